@@ -0,0 +1,85 @@
+//! How long a simulation tick takes with a raid's worth of drops in the arena.
+//! Run with `cargo bench --bench frame_time`.
+
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::command::Command;
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::input_state::InputState;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::Simulation;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+const SEED: u64 = 1337;
+const OBJECT_COUNTS: [usize; 3] = [50, 500, 5000];
+const TICKS: u32 = 60;
+const DROPS: [&str; 3] = ["#fire", "#sword", "#snake"];
+
+/// A simulation that is being played with `object_count` drops spread over the arena
+fn create_simulation(object_count: usize) -> (Simulation, Receiver<String>) {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.limits.drop_cooldown_seconds = 0.0;
+    config.limits.subscriber_drop_cooldown_seconds = 0.0;
+    config.limits.max_live_enemies = usize::MAX;
+    config.limits.refusal_replies = false;
+    let splash_ticks = config.game.duration_to_ticks(config.game.splash_duration());
+    let catalog = ObjectCatalog::default();
+    let mut simulation = Simulation::new(send_to_chat, ARENA_SIZE, SEED, config, catalog);
+    for _ in 0..splash_ticks {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let commands: Vec<Command> = (0..object_count)
+        .map(|index| {
+            let message = format!(
+                "{} {}",
+                DROPS[index % DROPS.len()],
+                index % simulation.config().game.drop_zone_count as usize
+            );
+            Command::new(
+                &message,
+                Chatter::new(format!("raider{}", index), (0, 0, 0), false),
+                simulation.config(),
+                simulation.catalog(),
+                &mut rng,
+            )
+            .unwrap()
+            .unwrap()
+        })
+        .collect();
+    simulation.tick(&InputState::default(), commands);
+    // spread out rather than stacked in the drop zones
+    for game_object in simulation.game_objects_mut().iter_mut().skip(1) {
+        game_object.location.x = rng.gen_range(0.0, ARENA_SIZE.0 - game_object.location.w);
+        game_object.location.y = rng.gen_range(0.0, ARENA_SIZE.1 - game_object.location.h);
+    }
+    (simulation, receive_from_game)
+}
+
+fn main() {
+    let walk = InputState {
+        right: true,
+        ..InputState::default()
+    };
+    for object_count in &OBJECT_COUNTS {
+        let (mut simulation, _receive_from_game) = create_simulation(*object_count);
+        let objects_at_start = simulation.game_objects().len();
+
+        let started = Instant::now();
+        for _ in 0..TICKS {
+            simulation.tick(&walk, vec![]);
+        }
+        let frame_time: Duration = started.elapsed() / TICKS;
+
+        println!(
+            "{:>5} objects: {:>10.3?} per tick ({} in the arena at the start)",
+            object_count, frame_time, objects_at_start
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use get_the_streamer_game::chat_transport::{apply_flags, IncomingChat, LocalIrcServer};
+use std::env;
+use std::io::{self, BufRead};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:6667";
+
+/// A pretend Twitch chat to play against, start the game with `--irc 127.0.0.1:6667`.
+/// Every line typed in here is sent to the game as `name: message`, tags can be added
+/// after the name with the same flags scenarios use, like `name sub,bits=500: message`.
+fn main() {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_owned());
+    let server = match LocalIrcServer::start(&address) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("could not start the irc server on {}: {}", address, error);
+            return;
+        }
+    };
+    println!(
+        "Listening on {}, type chat messages as `name: message`",
+        server.address()
+    );
+
+    let (send_line, receive_line) = std::sync::mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if send_line.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        if let Some(message) = server.receive_from_game(Duration::from_millis(50)) {
+            println!("game: {}", message);
+        }
+        for line in receive_line.try_iter() {
+            let (sender, message) = line.split_once(": ").unwrap_or(("chatter", &line));
+            let (name, flags) = sender.split_once(' ').unwrap_or((sender, "-"));
+            let mut chat = IncomingChat::new(name, message);
+            if let Err(error) = apply_flags(&mut chat, flags.trim()) {
+                eprintln!("{}", error);
+                continue;
+            }
+            if let Err(error) = server.send_chat(&chat) {
+                eprintln!("error sending chat: {}", error);
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::chatter::Chatter;
+use crate::vote::count_in_order;
+
+/// What chat can make the boss do in boss mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossAction {
+    Left,
+    Right,
+    /// Jump and come crashing down
+    Slam,
+    /// Spit something at the streamer
+    Spit,
+}
+
+impl BossAction {
+    pub fn parse(message: &str) -> Option<BossAction> {
+        match message.split_whitespace().next()? {
+            "#left" => Some(BossAction::Left),
+            "#right" => Some(BossAction::Right),
+            "#slam" => Some(BossAction::Slam),
+            "#spit" => Some(BossAction::Spit),
+            _ => None,
+        }
+    }
+}
+
+/// Chat's votes on the boss's next move, everyone gets one vote per window
+#[derive(Debug, Default)]
+pub struct BossTally {
+    /// In the order chatters first voted
+    votes: Vec<(Chatter, BossAction)>,
+    closes_at: Option<u64>,
+}
+
+impl BossTally {
+    pub fn cast(&mut self, chatter: Chatter, action: BossAction, tick: u64, window_ticks: u64) {
+        self.closes_at
+            .get_or_insert(tick.saturating_add(window_ticks));
+        match self
+            .votes
+            .iter_mut()
+            .find(|(voter, _action)| voter.name == chatter.name)
+        {
+            Some((_voter, existing_action)) => *existing_action = action,
+            None => self.votes.push((chatter, action)),
+        }
+    }
+
+    /// The action most of chat wanted once the window is over, along with the first chatter
+    /// who asked for it so they get the credit for what the boss does
+    pub fn decide(&mut self, tick: u64) -> Option<(BossAction, Chatter)> {
+        if self.closes_at.is_none_or(|closes_at| tick < closes_at) {
+            return None;
+        }
+        let (action, _votes) = count_in_order(self.votes.iter().map(|(_voter, action)| *action))
+            .into_iter()
+            .next()?;
+        let leader = self
+            .votes
+            .iter()
+            .find(|(_voter, vote)| *vote == action)
+            .map(|(voter, _vote)| voter.clone())?;
+        self.clear();
+        Some((action, leader))
+    }
+
+    pub fn clear(&mut self) {
+        self.votes.clear();
+        self.closes_at = None;
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use ggez::graphics::Rect;
+use ggez::nalgebra::Point2;
+
+use crate::chatter::Chatter;
+use crate::game_object::GameObject;
+use crate::game_object_type::GameObjectType;
+use crate::hitbox::PlacedHitbox;
+
+/// Wide enough that most drops only sit in one or two cells
+const CELL_SIZE: f32 = 128.0;
+
+/// What collision queries see of a game object, as it was at the start of the tick
+#[derive(Debug, Clone)]
+pub struct Collider {
+    /// Where the object is in what the broadphase was made from
+    pub index: usize,
+    pub location: Rect,
+    pub hitbox: PlacedHitbox,
+    pub my_type: GameObjectType,
+    pub chatter: Option<Chatter>,
+    pub object_name: Option<String>,
+}
+
+/// The collidable game objects sorted into a uniform grid once per tick, so that finding what
+/// something touches only looks at the objects around it instead of every object in the arena.
+/// Queries answer in the order the objects were added, which keeps the simulation deterministic.
+#[derive(Debug, Default)]
+pub struct Broadphase {
+    colliders: Vec<Collider>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    by_type: HashMap<GameObjectType, Vec<usize>>,
+}
+
+impl Broadphase {
+    pub fn new<'a>(game_objects: impl IntoIterator<Item = &'a GameObject>) -> Broadphase {
+        let mut broadphase = Broadphase::default();
+        for (object_index, game_object) in game_objects.into_iter().enumerate() {
+            if !game_object.collidable {
+                continue;
+            }
+            let index = broadphase.colliders.len();
+            let hitbox = game_object.placed_hitbox();
+            // rotated hitboxes can stick out of the sprite
+            for cell in cells_covering(&hitbox.bounds()) {
+                broadphase.cells.entry(cell).or_default().push(index);
+            }
+            broadphase
+                .by_type
+                .entry(game_object.my_type.clone())
+                .or_default()
+                .push(index);
+            broadphase.colliders.push(Collider {
+                index: object_index,
+                location: game_object.location,
+                hitbox,
+                my_type: game_object.my_type.clone(),
+                chatter: game_object.chatter.clone(),
+                object_name: game_object.object_name.clone(),
+            });
+        }
+        broadphase
+    }
+
+    /// Everything touching `hitbox`, in the order the objects were added
+    pub fn objects_touching(&self, hitbox: &PlacedHitbox) -> Vec<&Collider> {
+        let mut indexes: Vec<usize> = cells_covering(&hitbox.bounds())
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        // objects spanning several cells are found once for each
+        indexes.sort_unstable();
+        indexes.dedup();
+        indexes
+            .into_iter()
+            .map(|index| &self.colliders[index])
+            .filter(|collider| collider.hitbox.overlaps(hitbox))
+            .collect()
+    }
+
+    /// The object of `my_type` whose center is closest to `point`, the first one added wins ties
+    pub fn nearest_of_type(
+        &self,
+        my_type: &GameObjectType,
+        point: Point2<f32>,
+    ) -> Option<&Collider> {
+        let mut nearest: Option<(&Collider, f32)> = None;
+        for index in self.by_type.get(my_type).into_iter().flatten() {
+            let collider = &self.colliders[*index];
+            let location = collider.location;
+            let distance = (location.x + location.w / 2.0 - point.x).powi(2)
+                + (location.y + location.h / 2.0 - point.y).powi(2);
+            if nearest.is_none_or(|(_nearest, nearest_distance)| distance < nearest_distance) {
+                nearest = Some((collider, distance));
+            }
+        }
+        nearest.map(|(collider, _distance)| collider)
+    }
+}
+
+fn cells_covering(rect: &Rect) -> impl Iterator<Item = (i32, i32)> {
+    let first_column = (rect.x / CELL_SIZE).floor() as i32;
+    let last_column = ((rect.x + rect.w) / CELL_SIZE).floor() as i32;
+    let first_row = (rect.y / CELL_SIZE).floor() as i32;
+    let last_row = ((rect.y + rect.h) / CELL_SIZE).floor() as i32;
+    (first_column..=last_column)
+        .flat_map(move |column| (first_row..=last_row).map(move |row| (column, row)))
+}
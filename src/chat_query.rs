@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+
+use crate::chat_transport::IncomingChat;
+use crate::object_catalog::RANDOM_ALIASES;
+use crate::Simulation;
+
+/// How many chatters `!top` and `!round` list
+const STANDINGS_SIZE: usize = 5;
+
+/// Read-only questions chat can ask at any time, even between rounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatQuery {
+    /// The sender's points this round and all-time
+    Score,
+    /// The all-time top scorers
+    Top,
+    /// The standings for the current round
+    Round,
+    /// What can be dropped and how
+    Help,
+}
+
+impl ChatQuery {
+    pub fn parse(message: &str) -> Option<ChatQuery> {
+        match message.split_whitespace().next()? {
+            "!score" => Some(ChatQuery::Score),
+            "!top" => Some(ChatQuery::Top),
+            "!round" => Some(ChatQuery::Round),
+            "!help" => Some(ChatQuery::Help),
+            _ => None,
+        }
+    }
+}
+
+/// Answers chat queries, each chatter only gets an answer once per `chat.query_cooldown_seconds`
+/// so chat can't make the bot spam
+pub struct ChatQueries {
+    send_to_chat: Sender<String>,
+    /// The tick each chatter still waiting out their cooldown was last answered on
+    last_answered: HashMap<String, u64>,
+}
+
+impl ChatQueries {
+    pub fn new(send_to_chat: Sender<String>) -> ChatQueries {
+        ChatQueries {
+            send_to_chat,
+            last_answered: HashMap::new(),
+        }
+    }
+
+    /// Returns false when the message isn't a query, so it can be handled as a drop instead.
+    /// `high_scores` are the saved all-time scores, they don't have this round in them until it ends.
+    pub fn handle(
+        &mut self,
+        chat: &IncomingChat,
+        simulation: &Simulation,
+        high_scores: &HashMap<String, u128>,
+    ) -> bool {
+        let query = match ChatQuery::parse(&chat.message) {
+            Some(query) => query,
+            None => return false,
+        };
+
+        let config = simulation.config();
+        let cooldown_ticks = config.game.duration_to_ticks(config.chat.query_cooldown()) as u64;
+        let now = simulation.ticks();
+        // scores are kept under the display name, so everything here goes by it too
+        let name = chat.chatter_name();
+        // chatters whose cooldown is over are forgotten, so the map only holds recent askers
+        self.last_answered
+            .retain(|_, last_answered| now < last_answered.saturating_add(cooldown_ticks));
+        if self.last_answered.contains_key(name) {
+            return true;
+        }
+        self.last_answered.insert(name.to_owned(), now);
+
+        let answer = match query {
+            ChatQuery::Score => {
+                let round_score = simulation.scores().get(name).copied().unwrap_or(0);
+                let all_time_score = get_all_time_scores(simulation, high_scores)
+                    .get(name)
+                    .copied()
+                    .unwrap_or(0);
+                format!(
+                    "@{} you have {} points this round and {} all-time",
+                    name, round_score, all_time_score
+                )
+            }
+            ChatQuery::Top => {
+                let all_time_scores = get_all_time_scores(simulation, high_scores);
+                if all_time_scores.is_empty() {
+                    "Nobody has scored yet".to_owned()
+                } else {
+                    format!("All-time top: {}", format_standings(&all_time_scores))
+                }
+            }
+            ChatQuery::Round => {
+                if simulation.scores().is_empty() {
+                    format!("Nobody has scored in round {} yet", simulation.round())
+                } else {
+                    format!(
+                        "Round {}: {}",
+                        simulation.round(),
+                        format_standings(simulation.scores())
+                    )
+                }
+            }
+            ChatQuery::Help => {
+                let mut objects: Vec<&str> = simulation
+                    .catalog()
+                    .objects()
+                    .iter()
+                    .filter_map(|object| object.aliases.first().map(String::as_str))
+                    .collect();
+                objects.push(RANDOM_ALIASES[0]);
+                format!(
+                    "Drop things on the streamer with #<object> <column>, columns go from 0 to {}. You can drop {}. Ask for !score, !top or !round to see how you're doing",
+                    config.game.drop_zone_count - 1,
+                    objects.join(", ")
+                )
+            }
+        };
+
+        if let Err(error) = self.send_to_chat.send(answer) {
+            eprintln!("error sending query answer to chat: {}", error);
+        }
+        true
+    }
+}
+
+/// The saved scores, plus this round's when it hasn't been saved yet
+fn get_all_time_scores(
+    simulation: &Simulation,
+    high_scores: &HashMap<String, u128>,
+) -> HashMap<String, u128> {
+    let mut all_time_scores = high_scores.clone();
+    if !simulation.running_state().is_game_over() {
+        for (username, score) in simulation.scores() {
+            *all_time_scores.entry(username.to_owned()).or_insert(0) += score;
+        }
+    }
+    all_time_scores
+}
+
+/// `1. name (points), 2. ...` from highest to lowest
+fn format_standings(scores: &HashMap<String, u128>) -> String {
+    let mut standings: Vec<(&String, &u128)> = scores.iter().collect();
+    standings.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    standings
+        .into_iter()
+        .take(STANDINGS_SIZE)
+        .enumerate()
+        .map(|(index, (username, score))| format!("{}. {} ({})", index + 1, username, score))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+use crate::chat_transport::IncomingChat;
+use crate::config::ChatConfig;
+
+/// Chat waiting to be handled. The chat channel is emptied into it every frame so that
+/// messages are timestamped when they arrive, then a few are handled each frame.
+/// Messages that waited too long are thrown away, drops should land close to when chat sent them.
+#[derive(Debug)]
+pub struct ChatQueue {
+    messages: VecDeque<(Instant, IncomingChat)>,
+    messages_per_frame: usize,
+    max_message_age: Duration,
+    max_queued_messages: usize,
+    dropped_stale: u64,
+    dropped_full: u64,
+}
+
+impl ChatQueue {
+    pub fn new(config: &ChatConfig) -> ChatQueue {
+        ChatQueue {
+            messages: VecDeque::new(),
+            messages_per_frame: config.messages_per_frame,
+            max_message_age: config.max_message_age(),
+            max_queued_messages: config.max_queued_messages,
+            dropped_stale: 0,
+            dropped_full: 0,
+        }
+    }
+
+    /// Moves everything chat has sent into the queue, throwing away the oldest messages
+    /// when it's full
+    pub fn receive(&mut self, receive_from_chat: &Receiver<IncomingChat>, now: Instant) {
+        for chat in receive_from_chat.try_iter() {
+            self.messages.push_back((now, chat));
+        }
+        while self.messages.len() > self.max_queued_messages {
+            self.messages.pop_front();
+            self.dropped_full += 1;
+        }
+    }
+
+    /// The messages to handle this frame, oldest first
+    pub fn take(&mut self, now: Instant) -> Vec<IncomingChat> {
+        let mut messages = vec![];
+        while messages.len() < self.messages_per_frame {
+            let (received_at, chat) = match self.messages.pop_front() {
+                Some(message) => message,
+                None => break,
+            };
+            if now.duration_since(received_at) > self.max_message_age {
+                self.dropped_stale += 1;
+                continue;
+            }
+            messages.push(chat);
+        }
+        messages
+    }
+
+    /// How many messages are waiting
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Messages thrown away because they waited longer than `chat.max_message_age_seconds`
+    pub fn dropped_stale(&self) -> u64 {
+        self.dropped_stale
+    }
+
+    /// Messages thrown away because there were more than `chat.max_queued_messages` waiting
+    pub fn dropped_full(&self) -> u64 {
+        self.dropped_full
+    }
+}
@@ -0,0 +1,158 @@
+use std::env;
+use std::net::TcpStream;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use eyre::{eyre, Result};
+use twitchchat::messages::{Commands, Privmsg};
+use twitchchat::{commands, Decoder, Encoder, FromIrcMessage, UserConfig, TWITCH_IRC_ADDRESS};
+
+use super::{lock, ChatTransport, IncomingChat};
+
+/// Connects to Twitch chat, or anything else that speaks Twitch's flavor of IRC
+#[derive(Debug)]
+pub struct IrcChatTransport {
+    address: String,
+    user_config: UserConfig,
+    channels: Vec<String>,
+}
+
+impl IrcChatTransport {
+    pub fn new(address: &str, name: &str, token: &str, channels: Vec<String>) -> Result<Self> {
+        let user_config = UserConfig::builder()
+            .name(name)
+            .token(token)
+            .enable_all_capabilities()
+            .build()?;
+
+        Ok(IrcChatTransport {
+            address: address.to_owned(),
+            user_config,
+            channels,
+        })
+    }
+
+    /// Logs in without an account, Twitch only lets these read chat but the local irc server
+    /// doesn't mind
+    pub fn anonymous(address: &str, channels: Vec<String>) -> Result<Self> {
+        let user_config = UserConfig::builder()
+            .anonymous()
+            .enable_all_capabilities()
+            .build()?;
+
+        Ok(IrcChatTransport {
+            address: address.to_owned(),
+            user_config,
+            channels,
+        })
+    }
+
+    /// Twitch chat, logging in with `TWITCH_NAME`, `TWITCH_TOKEN` and `TWITCH_CHANNEL` from the
+    /// environment or the .env file
+    pub fn twitch() -> Result<Self> {
+        dotenv::dotenv().ok();
+        let name = get_env_var("TWITCH_NAME")?;
+        let token = get_env_var("TWITCH_TOKEN")?;
+        let channels = get_env_var("TWITCH_CHANNEL")?
+            .split(',')
+            .map(ToString::to_string)
+            .collect();
+
+        Self::new(TWITCH_IRC_ADDRESS, &name, &token, channels)
+    }
+}
+
+impl ChatTransport for IrcChatTransport {
+    fn run(
+        self: Box<Self>,
+        send_to_game: Sender<IncomingChat>,
+        receive_from_game: Receiver<String>,
+    ) -> Result<()> {
+        let stream = TcpStream::connect(&self.address)?;
+        let encoder = Arc::new(Mutex::new(Encoder::new(stream.try_clone()?)));
+
+        {
+            let mut encoder = lock(&encoder);
+            encoder.encode(commands::register(&self.user_config))?;
+            for channel in &self.channels {
+                encoder.encode(commands::join(channel))?;
+            }
+        }
+
+        let chat_encoder = encoder.clone();
+        let channels = self.channels.clone();
+        thread::spawn(move || {
+            for message in receive_from_game {
+                for channel in &channels {
+                    if let Err(error) =
+                        lock(&chat_encoder).encode(commands::privmsg(channel, &message))
+                    {
+                        eprintln!("error sending message to chat: {}", error);
+                    }
+                }
+            }
+        });
+
+        for irc_message in Decoder::new(stream) {
+            let command = match Commands::from_irc(irc_message?) {
+                Ok(command) => command,
+                Err(error) => {
+                    eprintln!("error reading message from chat: {}", error);
+                    continue;
+                }
+            };
+            let incoming_chat = match command {
+                Commands::Privmsg(privmsg) => IncomingChat::from(&privmsg),
+                Commands::Ping(ping) => {
+                    lock(&encoder).encode(commands::pong(ping.token()))?;
+                    continue;
+                }
+                _ => continue,
+            };
+            if send_to_game.send(incoming_chat).is_err() {
+                // the game has closed
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&Privmsg<'_>> for IncomingChat {
+    fn from(privmsg: &Privmsg<'_>) -> Self {
+        let badges = privmsg
+            .tags()
+            .get("badges")
+            .map(|badges| {
+                badges
+                    .split(',')
+                    .filter_map(|badge| badge.split('/').next())
+                    .filter(|name| !name.is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        IncomingChat {
+            name: privmsg.name().to_owned(),
+            display_name: privmsg.display_name().map(ToString::to_string),
+            color: privmsg
+                .color()
+                .map_or((0, 0, 0), |color| (color.rgb.0, color.rgb.1, color.rgb.2)),
+            subscriber: privmsg.is_subscriber(),
+            badges,
+            bits: privmsg.bits().unwrap_or(0),
+            custom_reward_id: privmsg
+                .tags()
+                .get("custom-reward-id")
+                .filter(|reward_id| !reward_id.is_empty())
+                .map(ToString::to_string),
+            message: privmsg.data().to_owned(),
+        }
+    }
+}
+
+fn get_env_var(key: &str) -> Result<String> {
+    env::var(key).map_err(|error| eyre!("{}: {}", key, error))
+}
@@ -0,0 +1,206 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use eyre::Result;
+
+use super::{lock, IncomingChat};
+
+const SERVER_NAME: &str = "tmi.twitch.tv";
+
+/// A stand-in for Twitch's chat server that speaks just enough IRC, tags included,
+/// for the game to connect to it. Chat messages are made up with `send_chat`.
+pub struct LocalIrcServer {
+    address: SocketAddr,
+    clients: Arc<Mutex<Vec<Client>>>,
+    receive_from_game: Receiver<String>,
+}
+
+struct Client {
+    stream: TcpStream,
+    channels: Vec<String>,
+}
+
+impl LocalIrcServer {
+    /// Use port 0 to let the operating system pick a free one, `address` tells you which
+    pub fn start(address: &str) -> Result<LocalIrcServer> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let clients = Arc::new(Mutex::new(vec![]));
+        let (send_from_game, receive_from_game) = channel();
+
+        let accepted_clients = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let client_index = {
+                    let mut clients = lock(&accepted_clients);
+                    match stream.try_clone() {
+                        Ok(stream) => clients.push(Client {
+                            stream,
+                            channels: vec![],
+                        }),
+                        Err(error) => {
+                            eprintln!("error accepting irc client: {}", error);
+                            continue;
+                        }
+                    }
+                    clients.len() - 1
+                };
+                let clients = accepted_clients.clone();
+                let send_from_game = send_from_game.clone();
+                thread::spawn(move || handle_client(stream, client_index, clients, send_from_game));
+            }
+        });
+
+        Ok(LocalIrcServer {
+            address,
+            clients,
+            receive_from_game,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// How many clients have joined a channel, chat is only sent to those
+    pub fn joined_clients(&self) -> usize {
+        lock(&self.clients)
+            .iter()
+            .filter(|client| !client.channels.is_empty())
+            .count()
+    }
+
+    /// Send a chat message to every channel that has been joined, as a tagged PRIVMSG like Twitch does
+    pub fn send_chat(&self, chat: &IncomingChat) -> Result<()> {
+        for client in lock(&self.clients).iter_mut() {
+            for channel in &client.channels {
+                client
+                    .stream
+                    .write_all(format_privmsg(chat, channel).as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The next thing the game said in chat
+    pub fn receive_from_game(&self, timeout: Duration) -> Option<String> {
+        self.receive_from_game.recv_timeout(timeout).ok()
+    }
+}
+
+fn handle_client(
+    stream: TcpStream,
+    client_index: usize,
+    clients: Arc<Mutex<Vec<Client>>>,
+    send_from_game: Sender<String>,
+) {
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(error) => {
+            eprintln!("error handling irc client: {}", error);
+            return;
+        }
+    };
+    let mut nick = "justinfan1234".to_owned();
+
+    for line in BufReader::new(stream).lines().map_while(Result::ok) {
+        let (command, arguments) = line.split_once(' ').unwrap_or((&line, ""));
+        let reply = match command {
+            "CAP" => Some(format!(
+                ":{} CAP * ACK {}\r\n",
+                SERVER_NAME,
+                arguments.trim_start_matches("REQ ")
+            )),
+            "NICK" => {
+                nick = arguments.to_owned();
+                Some(format!(
+                    ":{} 001 {} :Welcome, GLHF!\r\n:{} 376 {} :>\r\n",
+                    SERVER_NAME, nick, SERVER_NAME, nick
+                ))
+            }
+            "JOIN" => {
+                if let Some(client) = lock(&clients).get_mut(client_index) {
+                    client.channels.push(arguments.to_owned());
+                }
+                Some(format!(
+                    ":{}!{}@{}.{} JOIN {}\r\n",
+                    nick, nick, nick, SERVER_NAME, arguments
+                ))
+            }
+            "PING" => Some(format!(
+                ":{} PONG {} {}\r\n",
+                SERVER_NAME, SERVER_NAME, arguments
+            )),
+            "PRIVMSG" => {
+                if let Some((_channel, message)) = arguments.split_once(" :") {
+                    // nobody might be listening, which is fine
+                    send_from_game.send(message.to_owned()).ok();
+                }
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(reply) = reply {
+            if writer.write_all(reply.as_bytes()).is_err() {
+                break;
+            }
+        }
+    }
+
+    if let Some(client) = lock(&clients).get_mut(client_index) {
+        client.channels.clear();
+    }
+}
+
+fn format_privmsg(chat: &IncomingChat, channel: &str) -> String {
+    let mut badges: Vec<String> = chat
+        .badges
+        .iter()
+        .map(|badge| format!("{}/1", badge))
+        .collect();
+    // twitch marks subscribers with a badge
+    if chat.subscriber && !chat.has_badge("subscriber") {
+        badges.push("subscriber/1".to_owned());
+    }
+    let (red, green, blue) = chat.color;
+    let mut tags = vec![
+        format!("badges={}", badges.join(",")),
+        format!("color=#{:02X}{:02X}{:02X}", red, green, blue),
+        format!(
+            "display-name={}",
+            escape_tag_value(chat.display_name.as_deref().unwrap_or(&chat.name))
+        ),
+        format!("mod={}", chat.has_badge("moderator") as u8),
+        format!("subscriber={}", chat.subscriber as u8),
+    ];
+    if chat.bits > 0 {
+        tags.push(format!("bits={}", chat.bits));
+    }
+    if let Some(reward_id) = &chat.custom_reward_id {
+        tags.push(format!("custom-reward-id={}", escape_tag_value(reward_id)));
+    }
+
+    format!(
+        "@{} :{}!{}@{}.{} PRIVMSG {} :{}\r\n",
+        tags.join(";"),
+        chat.name,
+        chat.name,
+        chat.name,
+        SERVER_NAME,
+        channel,
+        chat.message
+    )
+}
+
+/// Tag values can't hold spaces or semicolons, IRCv3 has escapes for them
+fn escape_tag_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\:")
+        .replace(' ', "\\s")
+}
@@ -0,0 +1,80 @@
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use eyre::Result;
+use rand::Rng;
+
+use super::{ChatTransport, IncomingChat};
+
+/// Enough to hit each of the default premium drops
+const CHEER_AMOUNTS: [u64; 3] = [100, 500, 1000];
+
+/// Bots that drop random objects into random columns, for playing without Twitch
+#[derive(Debug)]
+pub struct MockChatTransport {
+    pub num_bot_users: u64,
+    pub initial_commands_delay: Duration,
+    pub min_command_interval_ms: u64,
+    pub max_command_interval_ms: u64,
+    /// How often a bot cheers with its drop, between 0.0 and 1.0
+    pub cheer_chance: f64,
+}
+
+impl ChatTransport for MockChatTransport {
+    fn run(
+        self: Box<Self>,
+        send_to_game: Sender<IncomingChat>,
+        receive_from_game: Receiver<String>,
+    ) -> Result<()> {
+        // nobody is reading what the game says
+        thread::spawn(move || receive_from_game.into_iter().for_each(drop));
+
+        //The commands we'll be selecting from and the number of times each will be in the selection pool
+        let command_occurences = [("fire", 1), ("sword", 1), ("snake", 1), ("heart", 1)];
+
+        //Generate command pool
+        let command_pool = command_occurences
+            .iter()
+            .fold(Vec::<&str>::new(), |mut v, c| {
+                v.extend(vec![c.0; c.1].iter());
+                v
+            });
+
+        let mock_user_names: Vec<String> = (1..self.num_bot_users.max(2))
+            .map(|u| format!("test_bot{}", u))
+            .collect();
+        let mut rng = rand::thread_rng();
+
+        thread::sleep(self.initial_commands_delay);
+
+        loop {
+            let message = format!(
+                "#{command} {column}",
+                command = command_pool[rng.gen_range(0, command_pool.len())],
+                column = rng.gen_range(0, 10)
+            );
+            let mock_user = &mock_user_names[rng.gen_range(0, mock_user_names.len())];
+            let bits = if rng.gen_bool(self.cheer_chance) {
+                CHEER_AMOUNTS[rng.gen_range(0, CHEER_AMOUNTS.len())]
+            } else {
+                0
+            };
+            if send_to_game
+                .send(IncomingChat {
+                    bits,
+                    ..IncomingChat::new(mock_user, &message)
+                })
+                .is_err()
+            {
+                // the game has closed
+                return Ok(());
+            }
+
+            thread::sleep(Duration::from_millis(rng.gen_range(
+                self.min_command_interval_ms,
+                self.max_command_interval_ms,
+            )));
+        }
+    }
+}
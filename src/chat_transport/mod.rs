@@ -0,0 +1,67 @@
+mod irc_chat_transport;
+mod local_irc_server;
+mod mock_chat_transport;
+mod scenario;
+
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Mutex, MutexGuard};
+
+use eyre::Result;
+
+pub use irc_chat_transport::IrcChatTransport;
+pub use local_irc_server::LocalIrcServer;
+pub use mock_chat_transport::MockChatTransport;
+pub use scenario::{apply_flags, Scenario, ScenarioChatTransport, ScenarioMessage};
+
+/// Somewhere chat messages come from, like Twitch or a bot for testing.
+/// The game only ever sees `IncomingChat`, so it doesn't care which one is used.
+pub trait ChatTransport: Send {
+    /// Runs until the connection closes, sending chat to the game and the game's messages to chat
+    fn run(
+        self: Box<Self>,
+        send_to_game: Sender<IncomingChat>,
+        receive_from_game: Receiver<String>,
+    ) -> Result<()>;
+}
+
+/// A chat message along with everything we know about who sent it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IncomingChat {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub color: (u8, u8, u8),
+    pub subscriber: bool,
+    /// Badge names like `broadcaster`, `moderator` or `subscriber`
+    pub badges: Vec<String>,
+    /// Bits cheered with this message
+    pub bits: u64,
+    /// Set when the message came with a channel point reward redemption
+    pub custom_reward_id: Option<String>,
+    pub message: String,
+}
+
+impl IncomingChat {
+    pub fn new(name: &str, message: &str) -> IncomingChat {
+        IncomingChat {
+            name: name.to_owned(),
+            message: message.to_owned(),
+            ..IncomingChat::default()
+        }
+    }
+
+    /// The display name when there is one, that's what chat sees
+    pub fn chatter_name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn has_badge(&self, badge: &str) -> bool {
+        self.badges.iter().any(|name| name == badge)
+    }
+}
+
+/// A panicking thread shouldn't take the whole chat connection down with it
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use eyre::{bail, eyre, Result};
+
+use super::{ChatTransport, IncomingChat};
+
+/// A script of chat messages, each sent at a set time after the round starts.
+///
+/// Every line is `<seconds> <name> <color> <flags> <message>`, like
+/// `5.0 xithedwarf #FF0000 sub #sword 3`. Lines starting with # are comments.
+/// The flags are `-` for none, or any of `sub`, `bits=100`, `reward=<reward id>` and
+/// `badge=moderator` separated by commas, like `sub,bits=500`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    messages: Vec<ScenarioMessage>,
+    next_message: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioMessage {
+    pub at: Duration,
+    pub chat: IncomingChat,
+}
+
+impl Scenario {
+    pub fn load(path: impl AsRef<Path>) -> Result<Scenario> {
+        let path = path.as_ref();
+        let raw_scenario = fs::read_to_string(path)?;
+        Scenario::parse(&raw_scenario).map_err(|error| eyre!("{:?}: {}", path, error))
+    }
+
+    pub fn parse(raw_scenario: &str) -> Result<Scenario> {
+        let mut messages = vec![];
+        for (index, line) in raw_scenario.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let message =
+                parse_line(line).map_err(|error| eyre!("line {}: {}", index + 1, error))?;
+            messages.push(message);
+        }
+        // lines don't have to be in order, but they are sent in order
+        messages.sort_by_key(|message| message.at);
+
+        Ok(Scenario {
+            messages,
+            next_message: 0,
+        })
+    }
+
+    pub fn messages(&self) -> &[ScenarioMessage] {
+        &self.messages
+    }
+
+    /// How long after the round starts the last message is sent
+    pub fn duration(&self) -> Duration {
+        self.messages
+            .last()
+            .map_or(Duration::from_secs(0), |message| message.at)
+    }
+
+    /// The messages that are due by `elapsed` and haven't been taken yet
+    pub fn take_due(&mut self, elapsed: Duration) -> Vec<IncomingChat> {
+        let first = self.next_message;
+        while let Some(message) = self.messages.get(self.next_message) {
+            if message.at > elapsed {
+                break;
+            }
+            self.next_message += 1;
+        }
+        self.messages[first..self.next_message]
+            .iter()
+            .map(|message| message.chat.clone())
+            .collect()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_message == self.messages.len()
+    }
+}
+
+fn parse_line(line: &str) -> Result<ScenarioMessage> {
+    let mut rest = line;
+    let mut next_part = |name: &str| {
+        let part = rest.split_whitespace().next().ok_or_else(|| {
+            eyre!(
+                "missing the {}, lines look like `5.0 name #FF0000 sub #sword 3`",
+                name
+            )
+        })?;
+        // the message is whatever is left, spaces and all
+        rest = rest.trim_start()[part.len()..].trim();
+        Ok::<_, eyre::Report>(part)
+    };
+
+    let seconds: f32 = next_part("time")?
+        .parse()
+        .map_err(|_| eyre!("the time has to be a number of seconds"))?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        bail!("the time has to be a finite number of seconds that isn't negative");
+    }
+    let name = next_part("name")?;
+    let color = parse_color(next_part("color")?)?;
+    let flags = next_part("flags")?;
+    if rest.is_empty() {
+        bail!("missing the message, lines look like `5.0 name #FF0000 sub #sword 3`");
+    }
+
+    let mut chat = IncomingChat {
+        color,
+        ..IncomingChat::new(name, rest)
+    };
+    apply_flags(&mut chat, flags)?;
+    Ok(ScenarioMessage {
+        at: Duration::from_secs_f32(seconds),
+        chat,
+    })
+}
+
+/// Sets the tags Twitch would have sent from flags like `sub,bits=100`, `-` sets nothing
+pub fn apply_flags(chat: &mut IncomingChat, flags: &str) -> Result<()> {
+    if flags == "-" {
+        return Ok(());
+    }
+    for flag in flags.split(',') {
+        match flag.split_once('=') {
+            None if flag == "sub" => chat.subscriber = true,
+            Some(("bits", bits)) => {
+                chat.bits = bits
+                    .parse()
+                    .map_err(|_| eyre!("bits has to be a number, not `{}`", bits))?
+            }
+            Some(("reward", reward_id)) => chat.custom_reward_id = Some(reward_id.to_owned()),
+            Some(("badge", badge)) => chat.badges.push(badge.to_owned()),
+            _ => bail!(
+                "flags are `-` or `sub`, `bits=100`, `reward=<id>` and `badge=<name>` separated by commas, not `{}`",
+                flag
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn parse_color(color: &str) -> Result<(u8, u8, u8)> {
+    let hex = color.trim_start_matches('#');
+    let rgb = u32::from_str_radix(hex, 16)
+        .ok()
+        .filter(|_| hex.len() == 6)
+        .ok_or_else(|| eyre!("the color has to look like #FF0000, not `{}`", color))?;
+    Ok(((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+/// Plays a scenario in real time, waiting out the splash screen first
+#[derive(Debug)]
+pub struct ScenarioChatTransport {
+    pub scenario: Scenario,
+    pub splash_duration: Duration,
+}
+
+impl ChatTransport for ScenarioChatTransport {
+    fn run(
+        self: Box<Self>,
+        send_to_game: Sender<IncomingChat>,
+        receive_from_game: Receiver<String>,
+    ) -> Result<()> {
+        thread::spawn(move || {
+            for message in receive_from_game {
+                println!("chat: {}", message);
+            }
+        });
+
+        let mut scenario = self.scenario;
+        thread::sleep(self.splash_duration);
+        let round_started_at = Instant::now();
+        while !scenario.is_finished() {
+            for chat in scenario.take_due(round_started_at.elapsed()) {
+                if send_to_game.send(chat).is_err() {
+                    // the game has closed
+                    return Ok(());
+                }
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        Ok(())
+    }
+}
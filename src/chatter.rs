@@ -1,5 +1,7 @@
 use ggez::graphics::Color;
 
+use crate::team::Team;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Chatter {
     pub name: String,
@@ -7,6 +9,8 @@ pub struct Chatter {
     pub green: u8,
     pub blue: u8,
     pub is_subscriber: bool,
+    /// Only set in team mode
+    pub team: Option<Team>,
 }
 
 impl Chatter {
@@ -23,6 +27,7 @@ impl Chatter {
             green,
             blue,
             is_subscriber,
+            team: None,
         }
     }
 
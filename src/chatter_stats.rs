@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use eyre::{bail, Result};
+use ggez::{filesystem, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::utilities;
+
+/// Lives in the ggez user config directory, next to the high scores
+pub const CHATTER_STATS_FILE_NAME: &str = "chatter_stats.json";
+const FORMAT_VERSION: u32 = 1;
+
+/// Everything a chatter has done, either in one round or over every round they played
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatterStats {
+    /// How many of each object they dropped, by catalog name
+    pub drops: BTreeMap<String, u64>,
+    /// Drops that took a life from the streamer
+    pub hits_landed: u64,
+    /// Hearts of theirs that the streamer picked up
+    pub hearts_given: u64,
+    /// Hearts of theirs that took a life because the streamer already had `player.max_lives`
+    pub hearts_that_hurt: u64,
+    pub rounds_played: u64,
+    pub rounds_won_by_chat: u64,
+    /// Seconds since the unix epoch, from when the rounds they played in ended
+    pub first_seen: u64,
+    pub last_seen: u64,
+}
+
+impl ChatterStats {
+    pub fn total_drops(&self) -> u64 {
+        self.drops.values().sum()
+    }
+
+    /// The object they dropped the most, ties go to the first name alphabetically
+    pub fn favorite_drop(&self) -> Option<&str> {
+        self.drops
+            .iter()
+            .rev()
+            .max_by_key(|(_, count)| **count)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Adds a round's stats to the all-time stats, `now` is when the round ended
+    pub fn add_round(&mut self, round: &ChatterStats, now: u64) {
+        for (name, count) in &round.drops {
+            *self.drops.entry(name.clone()).or_insert(0) += count;
+        }
+        self.hits_landed += round.hits_landed;
+        self.hearts_given += round.hearts_given;
+        self.hearts_that_hurt += round.hearts_that_hurt;
+        self.rounds_played += round.rounds_played;
+        self.rounds_won_by_chat += round.rounds_won_by_chat;
+        if self.first_seen == 0 {
+            self.first_seen = now;
+        }
+        self.last_seen = now;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChatterStatsFile {
+    version: u32,
+    chatters: BTreeMap<String, ChatterStats>,
+}
+
+/// All-time chatter stats, saved the same way as the high scores
+#[derive(Debug, Clone)]
+pub struct ChatterStatsStore {
+    directory: PathBuf,
+}
+
+/// The stats that could be read, along with everything that was wrong with the files
+#[derive(Debug, Default)]
+pub struct LoadedChatterStats {
+    pub chatters: HashMap<String, ChatterStats>,
+    pub problems: Vec<String>,
+}
+
+impl ChatterStatsStore {
+    pub fn new(directory: impl Into<PathBuf>) -> ChatterStatsStore {
+        ChatterStatsStore {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn in_user_config_dir(context: &Context) -> ChatterStatsStore {
+        ChatterStatsStore::new(filesystem::user_config_dir(context))
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(CHATTER_STATS_FILE_NAME)
+    }
+
+    /// Never fails, anything that can't be read is reported in `problems` and left out
+    pub fn load(&self) -> LoadedChatterStats {
+        let mut problems = vec![];
+        let chatters = utilities::read_with_backup(&self.path(), parse_stats_file, &mut problems)
+            .unwrap_or_default();
+        LoadedChatterStats { chatters, problems }
+    }
+
+    pub fn save(&self, chatters: &HashMap<String, ChatterStats>) -> Result<()> {
+        let stats_file = ChatterStatsFile {
+            version: FORMAT_VERSION,
+            chatters: chatters
+                .iter()
+                .map(|(name, stats)| (name.clone(), stats.clone()))
+                .collect(),
+        };
+        utilities::write_atomically(&self.path(), &serde_json::to_string_pretty(&stats_file)?)
+    }
+}
+
+fn parse_stats_file(raw_stats: &str) -> Result<HashMap<String, ChatterStats>> {
+    let stats_file: ChatterStatsFile = serde_json::from_str(raw_stats)?;
+    if stats_file.version > FORMAT_VERSION {
+        bail!(
+            "written by a newer version of the game (format version {})",
+            stats_file.version
+        );
+    }
+    Ok(stats_file.chatters.into_iter().collect())
+}
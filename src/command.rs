@@ -1,69 +1,107 @@
+use std::time::Duration;
+
 use ggez::{nalgebra::Point2, Context, GameResult};
-use rand::{distributions::Standard, prelude::Distribution, Rng};
+use rand::Rng;
 
 use crate::{
-    draw_system::DrawSystem, draw_system::GameObjectDrawSystem, game_object::GameObject,
-    game_object_type::GameObjectType, life_system::FireLifeSystem, life_system::HeartLifeSystem,
-    life_system::LifeSystem, life_system::SnakeLifeSystem, life_system::SwordLifeSystem,
-    physics::FirePhysics, physics::HeartPhysics, physics::PhysicsSystem, physics::SnakePhysics,
-    physics::SwordPhysics, sprites::Sprite,
+    draw_system::GameObjectDrawSystem, game_object::GameObject, life_system::FireLifeSystem,
+    life_system::HeartLifeSystem, life_system::LifeSystem, life_system::SnakeLifeSystem,
+    life_system::SwordLifeSystem, physics::FirePhysics, physics::HeartPhysics,
+    physics::PhysicsSystem, physics::SnakePhysics, physics::SwordPhysics, sprites::Sprite,
 };
 
 use super::Chatter;
+use crate::config::{Config, PremiumDrop};
+use crate::hitbox::Hitbox;
+use crate::object_catalog::{
+    LifetimeRule, ObjectCatalog, ObjectDefinition, PhysicsPreset, RANDOM_ALIASES,
+};
 
 pub struct Command {
-    pub command_type: CommandType,
+    pub object: ObjectDefinition,
     pub id: u8,
     pub chatter: Chatter,
+    /// Set when this was paid for with bits or channel points, named after the premium drop
+    pub premium: Option<String>,
 }
 
 impl Command {
-    pub fn new(message: &str, chatter: Chatter) -> Result<Option<Command>, &'static str> {
+    pub fn new(
+        message: &str,
+        chatter: Chatter,
+        config: &Config,
+        catalog: &ObjectCatalog,
+        rng: &mut impl Rng,
+    ) -> Result<Option<Command>, &'static str> {
         if !message.starts_with('#') {
             return Ok(None);
         }
 
         let mut parts = message.split(' ');
         if let Some(command) = parts.next() {
-            let id = Self::get_id_from_message(parts.next())?;
-            match command {
-                "#fire" => Ok(Some(Command {
-                    command_type: CommandType::Fire,
-                    id,
-                    chatter,
-                })),
-                "#sword" => Ok(Some(Command {
-                    command_type: CommandType::Sword,
-                    id,
-                    chatter,
-                })),
-                "#snake" | "#snek" => Ok(Some(Command {
-                    command_type: CommandType::Snake,
-                    id,
-                    chatter,
-                })),
-                "#heart" => Ok(Some(Command {
-                    command_type: CommandType::Heart,
-                    id,
-                    chatter,
-                })),
-                "#random" | "#rand" | "#rng" => Ok(Some(Command {
-                    command_type: CommandType::random(),
-                    id,
-                    chatter,
-                })),
-                _ => Ok(None),
-            }
+            let alias = command.trim_start_matches('#');
+            let object = if RANDOM_ALIASES.contains(&alias) {
+                None
+            } else if let Some(object) = catalog.find_by_alias(alias) {
+                Some(object)
+            } else {
+                return Ok(None);
+            };
+            let id = Self::get_id_from_message(parts.next(), config.game.drop_zone_count, rng)?;
+            let object = object.unwrap_or_else(|| catalog.choose_random(rng));
+            Ok(Some(Command {
+                object: object.clone(),
+                id,
+                chatter,
+                premium: None,
+            }))
         } else {
             Ok(None)
         }
     }
 
-    fn get_id_from_message(message_part: Option<&str>) -> Result<u8, &'static str> {
+    /// Everything a cheer or reward redemption drops. The first one goes into the column named
+    /// in the message if there is one, the rest are spread out over random columns.
+    pub fn new_premium(
+        drop: &PremiumDrop,
+        message: &str,
+        chatter: Chatter,
+        config: &Config,
+        catalog: &ObjectCatalog,
+        rng: &mut impl Rng,
+    ) -> Vec<Command> {
+        let mut object = match catalog.get(&drop.object) {
+            Some(object) => object.clone(),
+            None => return vec![],
+        };
+        object.scale *= drop.scale;
+        let drop_zone_count = config.game.drop_zone_count;
+        let column = message
+            .split_whitespace()
+            .filter_map(|word| word.parse::<u8>().ok())
+            .find(|column| *column < drop_zone_count);
+
+        (0..drop.count)
+            .map(|index| Command {
+                object: object.clone(),
+                id: column
+                    .filter(|_| index == 0)
+                    .unwrap_or_else(|| rng.gen_range(0, drop_zone_count)),
+                chatter: chatter.clone(),
+                premium: Some(drop.name.clone()),
+            })
+            .collect()
+    }
+
+    fn get_id_from_message(
+        message_part: Option<&str>,
+        drop_zone_count: u8,
+        rng: &mut impl Rng,
+    ) -> Result<u8, &'static str> {
         if let Some(id) = message_part {
             match id.parse::<u8>() {
                 Ok(number) => {
-                    if number < crate::DROP_ZONE_COUNT {
+                    if number < drop_zone_count {
                         Ok(number)
                     } else {
                         Err("The given column is outside of the arena")
@@ -72,112 +110,92 @@ impl Command {
                 Err(_error) => Err("I couldn't tell what column to drop into"),
             }
         } else {
-            let mut rng = rand::thread_rng();
-            Ok(rng.gen_range(0, crate::DROP_ZONE_COUNT))
+            Ok(rng.gen_range(0, drop_zone_count))
         }
     }
 
+    /// Creates the game object without a draw system so that it can be simulated headless,
+    /// use `create_draw_system` to make it visible
     pub fn handle(
         &self,
         drop_zone_location: Point2<f32>,
-        context: &mut Context,
-    ) -> GameResult<GameObject> {
-        let scale = self.get_scale();
-        let sprite = self.get_sprite(context)?;
-        let label_color = if self.chatter.is_subscriber {
-            self.chatter.get_color()
-        } else {
-            ggez::graphics::WHITE
-        };
-        let label = Some((self.chatter.name.clone(), label_color));
-        let draw_system = GameObjectDrawSystem::new(Some(sprite), label, scale);
-        let size = draw_system.get_size().unwrap_or((50.0, 50.0));
-        let physics_system = self.get_physics();
-        let game_object = GameObject::new(
+        config: &Config,
+        rng: &mut impl Rng,
+    ) -> GameObject {
+        let size = self.object.get_size();
+        let physics_system = self.get_physics(config, rng);
+        let mut game_object = GameObject::new(
             drop_zone_location.x - size.0 / 2.0,
             drop_zone_location.y - size.1 / 2.0,
-            Some(Box::new(draw_system)),
+            None,
             size.0,
             size.1,
             physics_system,
             true,
             Some(self.chatter.clone()),
-            self.get_game_object_type(),
-            self.get_life_system(),
+            self.object.get_game_object_type(),
+            self.get_life_system(config),
         );
-        Ok(game_object)
-    }
-
-    fn get_scale(&self) -> f32 {
-        match self.command_type {
-            CommandType::Fire => 2.0,
-            CommandType::Sword => 3.0,
-            CommandType::Snake => 3.0,
-            CommandType::Heart => 1.5,
-        }
-    }
-
-    fn get_sprite(&self, context: &mut Context) -> GameResult<Sprite> {
-        match self.command_type {
-            CommandType::Fire => Sprite::new(context, "/LargeFlame.png", 4, 1),
-            CommandType::Sword => Sprite::new(context, "/item1BIT_sword.png", 1, 1),
-            CommandType::Snake => Sprite::new(context, "/snake.png", 4, 1),
-            CommandType::Heart => Sprite::new(context, "/heart.png", 1, 1),
-        }
+        game_object.object_name = Some(self.object.name.clone());
+        game_object.hitbox = Hitbox::new(self.object.hitbox.clone());
+        game_object
     }
 
-    fn get_physics(&self) -> Option<Box<dyn PhysicsSystem>> {
-        match self.command_type {
-            CommandType::Fire => Some(Box::new(FirePhysics::new())),
-            CommandType::Sword => Some(Box::new(SwordPhysics::new())),
-            CommandType::Snake => Some(Box::new(SnakePhysics::new())),
-            CommandType::Heart => Some(Box::new(HeartPhysics::new())),
-        }
-    }
-
-    fn get_life_system(&self) -> Option<Box<dyn LifeSystem>> {
-        match self.command_type {
-            CommandType::Fire => Some(Box::new(FireLifeSystem::new())),
-            CommandType::Sword => Some(Box::new(SwordLifeSystem::new())),
-            CommandType::Snake => Some(Box::new(SnakeLifeSystem::new())),
-            CommandType::Heart => Some(Box::new(HeartLifeSystem::new())),
-        }
+    /// `scale` is the object's own scale unless it was a premium drop that made it bigger
+    pub fn create_draw_system(
+        object: &ObjectDefinition,
+        chatter: Option<&Chatter>,
+        scale: f32,
+        context: &mut Context,
+    ) -> GameResult<GameObjectDrawSystem> {
+        let sprite = Sprite::new(
+            context,
+            &object.sprite.path,
+            object.sprite.frames_across,
+            object.sprite.frames_down,
+        )?;
+        let label = chatter.map(|chatter| {
+            let label_color = if let Some(team) = chatter.team {
+                team.color()
+            } else if chatter.is_subscriber {
+                chatter.get_color()
+            } else {
+                ggez::graphics::WHITE
+            };
+            (chatter.name.clone(), label_color)
+        });
+        Ok(GameObjectDrawSystem::new(Some(sprite), label, scale))
     }
 
-    fn get_game_object_type(&self) -> GameObjectType {
-        match self.command_type {
-            CommandType::Heart => GameObjectType::Heart,
-            _ => GameObjectType::Enemy,
+    fn get_physics(&self, config: &Config, rng: &mut impl Rng) -> Option<Box<dyn PhysicsSystem>> {
+        match self.object.physics {
+            PhysicsPreset::FallAndStay => Some(Box::new(FirePhysics::new())),
+            PhysicsPreset::Bounce {
+                max_bounce_speed,
+                bounciness,
+            } => Some(Box::new(SwordPhysics::new(
+                max_bounce_speed,
+                bounciness,
+                rng,
+            ))),
+            PhysicsPreset::Slither { speed } => Some(Box::new(SnakePhysics::new(
+                speed.unwrap_or(config.physics.snake_speed),
+            ))),
+            PhysicsPreset::FloatDown { gravity_scale } => {
+                Some(Box::new(HeartPhysics::new(gravity_scale)))
+            }
         }
     }
-}
-
-#[derive(PartialEq, Debug)]
-pub enum CommandType {
-    Fire,
-    Sword,
-    Snake,
-    Heart,
-}
-
-impl CommandType {
-    pub fn random() -> Self {
-        rand::random()
-    }
-}
-
-impl Distribution<CommandType> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> CommandType {
-        let random: f32 = rng.gen();
 
-        if random < 0.1 {
-            CommandType::Heart
-        } else if random >= 0.1 && random < 0.4 {
-            CommandType::Fire
-        } else if random >= 0.4 && random < 0.7 {
-            CommandType::Sword
-        } else {
-            CommandType::Snake
+    fn get_life_system(&self, config: &Config) -> Option<Box<dyn LifeSystem>> {
+        match self.object.lifetime {
+            LifetimeRule::Timed { seconds } => Some(Box::new(FireLifeSystem::new(
+                Duration::from_secs_f32(seconds),
+                config.game.framerate_target,
+            ))),
+            LifetimeRule::Bounces { count } => Some(Box::new(SwordLifeSystem::new(count))),
+            LifetimeRule::UntilOffScreen => Some(Box::new(SnakeLifeSystem::new())),
+            LifetimeRule::UntilPickedUp => Some(Box::new(HeartLifeSystem::new())),
         }
     }
 }
@@ -0,0 +1,806 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use eyre::{bail, eyre, Result};
+use ggez::{filesystem, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::input_state::PlayerNumber;
+use crate::object_catalog::ObjectCatalog;
+
+/// Lives in the ggez user config directory, next to the high scores
+pub const CONFIG_FILE_NAME: &str = "game_config.toml";
+/// Version 1 files had speeds in pixels per tick, they are converted when loaded
+const CONFIG_VERSION: u32 = 2;
+
+/// Gameplay tuning that streamers can change without recompiling.
+/// Every key is optional, anything left out keeps its default value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Files without one are from version 1, before the version was written
+    pub version: u32,
+    pub game: GameConfig,
+    pub physics: PhysicsConfig,
+    pub player: PlayerConfig,
+    pub chat: ChatConfig,
+    pub limits: LimitsConfig,
+    pub voting: VotingConfig,
+    pub teams: TeamsConfig,
+    pub coop: CoopConfig,
+    pub boss: BossConfig,
+    pub endless: EndlessConfig,
+    pub premium: PremiumConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub game_time_seconds: u64,
+    pub splash_duration_seconds: u64,
+    pub lives: u8,
+    pub drop_zone_count: u8,
+    pub framerate_target: u32,
+    /// Go back to the splash screen after the credits instead of quitting
+    pub session_mode: bool,
+    /// How long the credits show between rounds in session mode
+    pub intermission_seconds: u64,
+    /// Leave this out to get a different game every time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicsConfig {
+    /// Pixels per second squared
+    pub gravity_force: f32,
+    /// Pixels per second
+    pub snake_speed: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    /// Pixels per second squared while a direction is held
+    pub move_force: f32,
+    /// Pixels per second, negative numbers jump up
+    pub jump_force: f32,
+    /// How quickly the streamer slows down, higher stops sooner
+    pub friction: f32,
+    pub iframes_start: u8,
+    pub iframes_min: u8,
+    pub iframes_reduce_by: u8,
+    pub iframes_reduce_every: u32,
+    /// Hearts caught with this many lives take one away instead, 0 for no limit
+    pub max_lives: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChatConfig {
+    /// How long a chatter waits between answers to `!score`, `!top`, `!round` and `!help`
+    pub query_cooldown_seconds: u64,
+    /// How many chat messages are handled each frame, the rest wait for the next frames
+    pub messages_per_frame: usize,
+    /// Messages that waited longer than this are thrown away instead of dropping late
+    pub max_message_age_seconds: f32,
+    /// The oldest messages are thrown away when more than this many are waiting
+    pub max_queued_messages: usize,
+}
+
+/// Keeps a single chatter with a macro, or a raid, from filling the arena
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// How long a chatter waits between drops
+    pub drop_cooldown_seconds: f32,
+    /// Subscribers get to drop more often
+    pub subscriber_drop_cooldown_seconds: f32,
+    /// Drops are refused while this many objects that hurt the streamer are in the arena
+    pub max_live_enemies: usize,
+    /// Tell chat when a drop is refused
+    pub refusal_replies: bool,
+    /// Only one refusal is sent to chat in this long, so a raid can't make the bot spam
+    pub refusal_reply_cooldown_seconds: f32,
+}
+
+/// Chat votes on the next drop with `#vote sword 4` instead of everyone dropping their own,
+/// for channels big enough that one object per message is chaos
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VotingConfig {
+    /// Plain drops are ignored while this is on, bits and channel point drops still work
+    pub enabled: bool,
+    /// How long a vote stays open after the first vote comes in
+    pub window_seconds: f32,
+    /// Every this many voters adds another object to the winning drop
+    pub voters_per_extra_object: u32,
+    /// The most objects a single vote can drop
+    pub max_objects: u32,
+}
+
+/// Splits chat into a red and a blue team that compete for points
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TeamsConfig {
+    pub enabled: bool,
+}
+
+/// A second streamer plays on the same keyboard with the arrow keys and Enter, chat wins
+/// once both are out of lives
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoopConfig {
+    pub enabled: bool,
+}
+
+impl CoopConfig {
+    pub fn player_numbers(&self) -> &'static [PlayerNumber] {
+        if self.enabled {
+            &PlayerNumber::ALL
+        } else {
+            &PlayerNumber::ALL[..1]
+        }
+    }
+}
+
+/// Chat steers a single big boss with `#left`, `#right`, `#slam` and `#spit` instead of
+/// dropping things, the streamer wins early by jumping on it until it runs out of health
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BossConfig {
+    /// Plain drops are ignored while this is on, bits and channel point drops still work
+    pub enabled: bool,
+    /// The object from the object catalog the boss looks like
+    pub object: String,
+    /// The boss's size, in multiples of the object's sprite
+    pub scale: f32,
+    /// How many times the streamer has to land on the boss
+    pub health: u8,
+    /// The boss does whatever most of chat asked for in each window
+    pub window_seconds: f32,
+    /// How fast `#left` and `#right` move the boss, in pixels per second
+    pub speed: f32,
+    /// How high `#slam` jumps in pixels per second, negative numbers jump up
+    pub jump_force: f32,
+    /// The object from the object catalog `#spit` throws at the streamer
+    pub spit_object: String,
+    /// After every stomp the boss can't be hurt for this long
+    pub iframes_seconds: f32,
+}
+
+/// Rounds without a time limit, the streamer survives as long as they can while every wave
+/// makes things harder. Each `_per_wave` setting is added on again every wave.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndlessConfig {
+    pub enabled: bool,
+    pub wave_seconds: u64,
+    /// Extra gravity, as a fraction of `physics.gravity_force`
+    pub gravity_per_wave: f32,
+    /// Extra snake speed, as a fraction of `physics.snake_speed`
+    pub speed_per_wave: f32,
+    /// How much of what's left of the drop cooldowns is taken off
+    pub cooldown_reduction_per_wave: f32,
+    /// More enemies allowed in the arena at once
+    pub enemies_per_wave: usize,
+}
+
+/// Special drops for cheering bits or redeeming channel point rewards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PremiumConfig {
+    /// Checked in order, the first one that matches the message is dropped
+    pub drops: Vec<PremiumDrop>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PremiumDrop {
+    /// What chat is told was dropped, like `a swarm of snakes`
+    pub name: String,
+    /// The name of the object in the object catalog
+    pub object: String,
+    /// How many are dropped, all but the first go into random columns
+    #[serde(default = "default_premium_count")]
+    pub count: u32,
+    /// Multiplies the object's size
+    #[serde(default = "default_premium_scale")]
+    pub scale: f32,
+    /// Cheers of at least this many bits get this drop
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_bits: Option<u64>,
+    /// Cheers of more bits than this don't
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bits: Option<u64>,
+    /// Channel point redemptions of this reward get this drop, the id is in the
+    /// `custom-reward-id` tag of the redemption message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_id: Option<String>,
+}
+
+fn default_premium_count() -> u32 {
+    1
+}
+
+fn default_premium_scale() -> f32 {
+    1.0
+}
+
+impl PremiumConfig {
+    /// The drop for a message with these tags, reward redemptions win over cheers
+    pub fn find(&self, bits: u64, custom_reward_id: Option<&str>) -> Option<&PremiumDrop> {
+        if let Some(custom_reward_id) = custom_reward_id {
+            if let Some(drop) = self
+                .drops
+                .iter()
+                .find(|drop| drop.reward_id.as_deref() == Some(custom_reward_id))
+            {
+                return Some(drop);
+            }
+        }
+        if bits == 0 {
+            return None;
+        }
+        self.drops.iter().find(|drop| {
+            drop.min_bits.is_some_and(|min_bits| bits >= min_bits)
+                && drop.max_bits.is_none_or(|max_bits| bits <= max_bits)
+        })
+    }
+
+    /// Premium drops name objects from the catalog, which is loaded separately
+    pub fn validate_objects(&self, catalog: &ObjectCatalog) -> Result<()> {
+        for (index, drop) in self.drops.iter().enumerate() {
+            check(
+                catalog.get(&drop.object).is_some(),
+                &format!("premium.drops[{}].object", index),
+                "be the name of an object in the object catalog",
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for BossConfig {
+    fn default() -> Self {
+        BossConfig {
+            enabled: false,
+            object: "snake".to_owned(),
+            scale: 10.0,
+            health: 5,
+            window_seconds: 2.0,
+            speed: 360.0,
+            jump_force: -900.0,
+            spit_object: "fire".to_owned(),
+            iframes_seconds: 1.0,
+        }
+    }
+}
+
+impl Default for EndlessConfig {
+    fn default() -> Self {
+        EndlessConfig {
+            enabled: false,
+            wave_seconds: 30,
+            gravity_per_wave: 0.05,
+            speed_per_wave: 0.1,
+            cooldown_reduction_per_wave: 0.1,
+            enemies_per_wave: 5,
+        }
+    }
+}
+
+impl EndlessConfig {
+    pub fn wave_time(&self) -> Duration {
+        Duration::from_secs(self.wave_seconds)
+    }
+}
+
+impl Default for VotingConfig {
+    fn default() -> Self {
+        VotingConfig {
+            enabled: false,
+            window_seconds: 10.0,
+            voters_per_extra_object: 5,
+            max_objects: 5,
+        }
+    }
+}
+
+impl VotingConfig {
+    /// How many objects a vote with this many voters drops
+    pub fn object_count(&self, voters: usize) -> u32 {
+        (1 + voters.saturating_sub(1) as u32 / self.voters_per_extra_object).min(self.max_objects)
+    }
+}
+
+impl Default for PremiumConfig {
+    fn default() -> Self {
+        PremiumConfig {
+            drops: vec![
+                PremiumDrop {
+                    name: "a swarm of snakes".to_owned(),
+                    object: "snake".to_owned(),
+                    count: 5,
+                    scale: 1.0,
+                    min_bits: Some(100),
+                    max_bits: Some(499),
+                    reward_id: None,
+                },
+                PremiumDrop {
+                    name: "a giant sword".to_owned(),
+                    object: "sword".to_owned(),
+                    count: 1,
+                    scale: 3.0,
+                    min_bits: Some(500),
+                    max_bits: Some(999),
+                    reward_id: None,
+                },
+                PremiumDrop {
+                    name: "a rain of hearts".to_owned(),
+                    object: "heart".to_owned(),
+                    count: 8,
+                    scale: 1.0,
+                    min_bits: Some(1000),
+                    max_bits: None,
+                    reward_id: None,
+                },
+            ],
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            version: CONFIG_VERSION,
+            game: GameConfig::default(),
+            physics: PhysicsConfig::default(),
+            player: PlayerConfig::default(),
+            chat: ChatConfig::default(),
+            limits: LimitsConfig::default(),
+            voting: VotingConfig::default(),
+            teams: TeamsConfig::default(),
+            coop: CoopConfig::default(),
+            boss: BossConfig::default(),
+            endless: EndlessConfig::default(),
+            premium: PremiumConfig::default(),
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            game_time_seconds: 120,
+            splash_duration_seconds: 15,
+            lives: 3,
+            drop_zone_count: 10,
+            framerate_target: 60,
+            session_mode: false,
+            intermission_seconds: 30,
+            seed: None,
+        }
+    }
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        PhysicsConfig {
+            gravity_force: 1080.0,
+            snake_speed: 600.0,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        PlayerConfig {
+            move_force: 7200.0,
+            jump_force: -750.0,
+            friction: 9.75,
+            iframes_start: 120,
+            iframes_min: 10,
+            iframes_reduce_by: 10,
+            iframes_reduce_every: 720,
+            max_lives: 0,
+        }
+    }
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        ChatConfig {
+            query_cooldown_seconds: 30,
+            messages_per_frame: 10,
+            max_message_age_seconds: 5.0,
+            max_queued_messages: 500,
+        }
+    }
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        LimitsConfig {
+            drop_cooldown_seconds: 2.0,
+            subscriber_drop_cooldown_seconds: 1.0,
+            max_live_enemies: 40,
+            refusal_replies: true,
+            refusal_reply_cooldown_seconds: 10.0,
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn game_time(&self) -> Duration {
+        Duration::from_secs(self.game_time_seconds)
+    }
+
+    pub fn splash_duration(&self) -> Duration {
+        Duration::from_secs(self.splash_duration_seconds)
+    }
+
+    pub fn intermission(&self) -> Duration {
+        Duration::from_secs(self.intermission_seconds)
+    }
+
+    /// How long a single fixed simulation tick is, the `dt` physics is run with
+    pub fn tick_seconds(&self) -> f32 {
+        1.0 / self.framerate_target as f32
+    }
+
+    /// How many fixed simulation ticks fit in `seconds`, for the settings that can be fractions.
+    /// Huge numbers of seconds come out as `u64::MAX`, so add the ticks with `saturating_add`
+    pub fn seconds_to_ticks(&self, seconds: f32) -> u64 {
+        (seconds * self.framerate_target as f32) as u64
+    }
+
+    /// How many fixed simulation ticks fit in `duration`
+    pub fn duration_to_ticks(&self, duration: Duration) -> u32 {
+        (duration.as_secs_f32() * self.framerate_target as f32) as u32
+    }
+}
+
+impl ChatConfig {
+    pub fn query_cooldown(&self) -> Duration {
+        Duration::from_secs(self.query_cooldown_seconds)
+    }
+
+    /// Ages too long for a `Duration` never throw anything away
+    pub fn max_message_age(&self) -> Duration {
+        Duration::try_from_secs_f32(self.max_message_age_seconds).unwrap_or(Duration::MAX)
+    }
+}
+
+impl Config {
+    /// Loads the config file from the user config directory. When there isn't one yet
+    /// the defaults are written there so that streamers have something to edit.
+    pub fn load(context: &Context) -> Result<Config> {
+        let path = Self::get_path(context);
+        if !path.exists() {
+            let config = Config::default();
+            if let Err(error) = config.save(&path) {
+                eprintln!("Error writing default config to {:?}: {}", path, error);
+            }
+            return Ok(config);
+        }
+
+        let raw_config = fs::read_to_string(&path)?;
+        Config::from_toml(&raw_config).map_err(|error| eyre!("{:?}: {}", path, error))
+    }
+
+    pub fn from_toml(raw_config: &str) -> Result<Config> {
+        let mut raw_values: toml::Value = toml::from_str(raw_config)?;
+        let version = raw_values
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .unwrap_or(1);
+        if version > CONFIG_VERSION as i64 {
+            bail!(
+                "written by a newer version of the game (config version {})",
+                version
+            );
+        }
+        let config: Config = if version < 2 {
+            convert_from_per_tick_units(&mut raw_values);
+            toml::from_str(&toml::to_string(&raw_values)?)?
+        } else {
+            toml::from_str(raw_config)?
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn get_path(context: &Context) -> PathBuf {
+        filesystem::user_config_dir(context).join(CONFIG_FILE_NAME)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// A copy with the difficulty of an endless mode wave, wave 1 is the config as it is
+    pub fn for_wave(&self, wave: u32) -> Config {
+        let endless = &self.endless;
+        let waves = wave.saturating_sub(1) as f32;
+        let cooldown_scale = (1.0 - endless.cooldown_reduction_per_wave).powf(waves);
+        let mut config = self.clone();
+        config.physics.gravity_force *= 1.0 + endless.gravity_per_wave * waves;
+        config.physics.snake_speed *= 1.0 + endless.speed_per_wave * waves;
+        config.limits.drop_cooldown_seconds *= cooldown_scale;
+        config.limits.subscriber_drop_cooldown_seconds *= cooldown_scale;
+        config.limits.max_live_enemies +=
+            endless.enemies_per_wave * wave.saturating_sub(1) as usize;
+        config
+    }
+
+    /// Settings that name objects are checked against the object catalog, which is loaded separately
+    pub fn validate_objects(&self, catalog: &ObjectCatalog) -> Result<()> {
+        self.premium.validate_objects(catalog)?;
+        for (key, object) in [
+            ("boss.object", &self.boss.object),
+            ("boss.spit_object", &self.boss.spit_object),
+        ] {
+            check(
+                catalog.get(object).is_some(),
+                key,
+                "be the name of an object in the object catalog",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Catches values that parse fine but would break the game
+    pub fn validate(&self) -> Result<()> {
+        let game = &self.game;
+        let physics = &self.physics;
+        let player = &self.player;
+        let chat = &self.chat;
+        let limits = &self.limits;
+        let voting = &self.voting;
+
+        check(
+            game.game_time_seconds > 0,
+            "game.game_time_seconds",
+            "be above 0",
+        )?;
+        check(game.lives > 0, "game.lives", "be above 0")?;
+        check(
+            game.drop_zone_count > 0,
+            "game.drop_zone_count",
+            "be above 0",
+        )?;
+        check(
+            game.framerate_target > 0,
+            "game.framerate_target",
+            "be above 0",
+        )?;
+        check(
+            physics.gravity_force.is_finite() && physics.gravity_force >= 0.0,
+            "physics.gravity_force",
+            "be a number that isn't negative",
+        )?;
+        check(
+            physics.snake_speed.is_finite() && physics.snake_speed >= 0.0,
+            "physics.snake_speed",
+            "be a number that isn't negative",
+        )?;
+        check(
+            player.move_force.is_finite() && player.move_force >= 0.0,
+            "player.move_force",
+            "be a number that isn't negative",
+        )?;
+        check(
+            player.jump_force.is_finite() && player.jump_force < 0.0,
+            "player.jump_force",
+            "be a number below 0, negative numbers jump up",
+        )?;
+        check(
+            player.friction.is_finite() && player.friction >= 0.0,
+            "player.friction",
+            "be a number that isn't negative",
+        )?;
+        check(
+            player.iframes_min <= player.iframes_start,
+            "player.iframes_min",
+            "not be more than player.iframes_start",
+        )?;
+        check(
+            player.iframes_reduce_every > 0,
+            "player.iframes_reduce_every",
+            "be above 0",
+        )?;
+        check(
+            player.max_lives == 0 || player.max_lives >= game.lives,
+            "player.max_lives",
+            "be 0 or at least game.lives",
+        )?;
+        check(
+            chat.messages_per_frame > 0,
+            "chat.messages_per_frame",
+            "be above 0",
+        )?;
+        check(
+            chat.max_message_age_seconds.is_finite() && chat.max_message_age_seconds >= 0.0,
+            "chat.max_message_age_seconds",
+            "be a number that isn't negative",
+        )?;
+        check(
+            chat.max_queued_messages > 0,
+            "chat.max_queued_messages",
+            "be above 0",
+        )?;
+        check(
+            limits.drop_cooldown_seconds.is_finite() && limits.drop_cooldown_seconds >= 0.0,
+            "limits.drop_cooldown_seconds",
+            "be a number that isn't negative",
+        )?;
+        check(
+            limits.subscriber_drop_cooldown_seconds.is_finite()
+                && limits.subscriber_drop_cooldown_seconds >= 0.0,
+            "limits.subscriber_drop_cooldown_seconds",
+            "be a number that isn't negative",
+        )?;
+        check(
+            limits.max_live_enemies > 0,
+            "limits.max_live_enemies",
+            "be above 0",
+        )?;
+        check(
+            limits.refusal_reply_cooldown_seconds.is_finite()
+                && limits.refusal_reply_cooldown_seconds >= 0.0,
+            "limits.refusal_reply_cooldown_seconds",
+            "be a number that isn't negative",
+        )?;
+        check(
+            voting.window_seconds.is_finite() && voting.window_seconds > 0.0,
+            "voting.window_seconds",
+            "be a number above 0",
+        )?;
+        check(
+            voting.voters_per_extra_object > 0,
+            "voting.voters_per_extra_object",
+            "be above 0",
+        )?;
+        check(voting.max_objects > 0, "voting.max_objects", "be above 0")?;
+        check(
+            self.endless.wave_seconds > 0,
+            "endless.wave_seconds",
+            "be above 0",
+        )?;
+        check(
+            self.endless.gravity_per_wave.is_finite() && self.endless.gravity_per_wave >= 0.0,
+            "endless.gravity_per_wave",
+            "be a number that isn't negative",
+        )?;
+        check(
+            self.endless.speed_per_wave.is_finite() && self.endless.speed_per_wave >= 0.0,
+            "endless.speed_per_wave",
+            "be a number that isn't negative",
+        )?;
+        check(
+            (0.0..1.0).contains(&self.endless.cooldown_reduction_per_wave),
+            "endless.cooldown_reduction_per_wave",
+            "be at least 0.0 and below 1.0",
+        )?;
+        check(
+            self.boss.scale.is_finite() && self.boss.scale > 0.0,
+            "boss.scale",
+            "be a number above 0",
+        )?;
+        check(self.boss.health > 0, "boss.health", "be above 0")?;
+        check(
+            self.boss.window_seconds.is_finite() && self.boss.window_seconds > 0.0,
+            "boss.window_seconds",
+            "be a number above 0",
+        )?;
+        check(
+            self.boss.speed.is_finite() && self.boss.speed >= 0.0,
+            "boss.speed",
+            "be a number that isn't negative",
+        )?;
+        check(
+            self.boss.jump_force.is_finite() && self.boss.jump_force < 0.0,
+            "boss.jump_force",
+            "be a number below 0, negative numbers jump up",
+        )?;
+        check(
+            self.boss.iframes_seconds.is_finite() && self.boss.iframes_seconds >= 0.0,
+            "boss.iframes_seconds",
+            "be a number that isn't negative",
+        )?;
+        for (index, drop) in self.premium.drops.iter().enumerate() {
+            let key = |field: &str| format!("premium.drops[{}].{}", index, field);
+            check(drop.count > 0, &key("count"), "be above 0")?;
+            check(
+                drop.scale.is_finite() && drop.scale > 0.0,
+                &key("scale"),
+                "be a number above 0",
+            )?;
+            check(
+                drop.min_bits.is_some() || drop.reward_id.is_some(),
+                &key("min_bits"),
+                "be set when there's no reward_id, or nothing gets this drop",
+            )?;
+            check(
+                drop.max_bits.is_none() || drop.min_bits <= drop.max_bits,
+                &key("max_bits"),
+                "not be less than min_bits",
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Version 1 moved things a set amount every tick, so the game ran slower on machines that
+/// couldn't keep up. Speeds were in pixels per tick, forces in pixels per tick squared and
+/// friction was the part of the speed lost every tick. Only the keys in the file are converted,
+/// the ones left out get the new defaults.
+fn convert_from_per_tick_units(raw_values: &mut toml::Value) {
+    let ticks_per_second = raw_values
+        .get("game")
+        .and_then(|game| game.get("framerate_target"))
+        .and_then(toml::Value::as_integer)
+        .map_or(
+            GameConfig::default().framerate_target as f64,
+            |framerate_target| framerate_target as f64,
+        );
+    for (section, key) in &[
+        ("physics", "snake_speed"),
+        ("player", "jump_force"),
+        ("boss", "speed"),
+        ("boss", "jump_force"),
+    ] {
+        convert_value(raw_values, section, key, |speed| speed * ticks_per_second);
+    }
+    for (section, key) in &[("physics", "gravity_force"), ("player", "move_force")] {
+        convert_value(raw_values, section, key, |force| {
+            force * ticks_per_second * ticks_per_second
+        });
+    }
+    convert_value(raw_values, "player", "friction", |friction| {
+        -(1.0 - friction).ln() * ticks_per_second
+    });
+    if let Some(raw_config) = raw_values.as_table_mut() {
+        raw_config.insert(
+            "version".to_owned(),
+            toml::Value::Integer(CONFIG_VERSION as i64),
+        );
+    }
+}
+
+fn convert_value(
+    raw_values: &mut toml::Value,
+    section: &str,
+    key: &str,
+    convert: impl Fn(f64) -> f64,
+) {
+    let value = match raw_values
+        .get_mut(section)
+        .and_then(|section| section.get_mut(key))
+    {
+        Some(value) => value,
+        None => return,
+    };
+    let number = match value {
+        toml::Value::Float(number) => *number,
+        toml::Value::Integer(number) => *number as f64,
+        // left for the wrong type to be reported when the config is read
+        _ => return,
+    };
+    *value = toml::Value::Float(convert(number));
+}
+
+pub(crate) fn check(is_valid: bool, key: &str, requirement: &str) -> Result<()> {
+    if is_valid {
+        Ok(())
+    } else {
+        Err(eyre!("`{}` must {}", key, requirement))
+    }
+}
@@ -0,0 +1,59 @@
+use eyre::{bail, eyre, Result};
+
+/// Badges that are allowed to run `!gts` commands
+const CONTROL_BADGES: [&str; 2] = ["broadcaster", "moderator"];
+const USAGE: &str = "Use !gts pause, resume, start, end, ban <user> or lives +1";
+
+/// Commands for the streamer and their mods, so a raid flooding the arena can be dealt with
+/// from chat instead of killing the game
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    Pause,
+    Resume,
+    /// Skip the splash screen or the rest of the intermission
+    Start,
+    /// End the round now, the streamer wins
+    End,
+    /// Ignore everything this chatter drops for the rest of the session
+    Ban(String),
+    AddLives(u8),
+}
+
+impl ControlCommand {
+    /// `None` when the message isn't a `!gts` command at all, the error is the reply for chat
+    pub fn parse(message: &str) -> Option<Result<ControlCommand>> {
+        let mut words = message.split_whitespace();
+        if words.next()? != "!gts" {
+            return None;
+        }
+
+        Some(Self::parse_arguments(words.collect()))
+    }
+
+    fn parse_arguments(arguments: Vec<&str>) -> Result<ControlCommand> {
+        let command = match arguments.as_slice() {
+            ["pause"] => ControlCommand::Pause,
+            ["resume"] => ControlCommand::Resume,
+            ["start"] => ControlCommand::Start,
+            ["end"] => ControlCommand::End,
+            ["ban", name] => ControlCommand::Ban(name.trim_start_matches('@').to_lowercase()),
+            ["lives", lives] => {
+                let lives = lives
+                    .strip_prefix('+')
+                    .and_then(|lives| lives.parse().ok())
+                    .filter(|lives| *lives > 0)
+                    .ok_or_else(|| eyre!("Lives can only be added, like !gts lives +1"))?;
+                ControlCommand::AddLives(lives)
+            }
+            _ => bail!(USAGE),
+        };
+        Ok(command)
+    }
+
+    /// Only the broadcaster and moderators can control the game
+    pub fn is_allowed(badges: &[String]) -> bool {
+        badges
+            .iter()
+            .any(|badge| CONTROL_BADGES.contains(&badge.as_str()))
+    }
+}
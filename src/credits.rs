@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use super::utilities;
+use crate::chatter_stats::ChatterStats;
+use crate::personal_bests::SurvivalRun;
 use crate::running_state::RunningState;
+use crate::team::Team;
 use ggez::{
     graphics::DrawParam,
     graphics::Font,
@@ -10,11 +13,13 @@ use ggez::{
     nalgebra::Point2,
     Context, GameResult,
 };
-use rand::prelude::*;
 use rand::seq::IteratorRandom;
+use rand::Rng;
 
 const VELOCITY_Y: f32 = -2.0;
 const DEFAULT_STREAMER_WIN_MESSAGE: &str = "Streamer won!";
+const SESSION_LEADERBOARD_SIZE: usize = 10;
+const REGULARS_SIZE: usize = 5;
 const CONTRIBUTORS: [&str; 6] = [
     "brookspatton",
     "dmb1107",
@@ -29,19 +34,26 @@ pub struct Credits {
 }
 
 impl Credits {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         running_state: RunningState,
         context: &mut Context,
         screen_size: (f32, f32),
         high_scores: &HashMap<String, u128>,
         scores: &HashMap<String, u128>,
+        session_leaderboard: &[(String, u128)],
+        chatter_stats: &HashMap<String, ChatterStats>,
+        team_scores: &[(Team, u128)],
+        survival_run: Option<&SurvivalRun>,
+        personal_bests: &[SurvivalRun],
+        rng: &mut impl Rng,
     ) -> GameResult<Self> {
         let file_name = if matches!(running_state, RunningState::PlayerWon) {
             "streamer_wins_messages.txt"
         } else {
             "chat_wins_messages.txt"
         };
-        let snarky_message = Self::get_random_message(file_name);
+        let snarky_message = Self::get_random_message(file_name, rng);
         let mut credit_y = screen_size.1;
 
         let mut all_credits = vec![];
@@ -55,6 +67,74 @@ impl Credits {
             &mut credit_y,
         );
         Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+        if let Some(survival_run) = survival_run {
+            let survived = format!(
+                "You Survived {} and Reached Wave {}",
+                survival_run.format_time(),
+                survival_run.wave
+            );
+            Self::create_credit(
+                context,
+                screen_size,
+                &survived,
+                Some(75.0),
+                &mut all_credits,
+                &mut credit_y,
+            );
+            if personal_bests.first() == Some(survival_run) {
+                Self::create_credit(
+                    context,
+                    screen_size,
+                    "New Personal Best!",
+                    None,
+                    &mut all_credits,
+                    &mut credit_y,
+                );
+            }
+            Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+            Self::create_credit(
+                context,
+                screen_size,
+                "Personal Bests",
+                None,
+                &mut all_credits,
+                &mut credit_y,
+            );
+            personal_bests.iter().for_each(|run| {
+                let title = format!("{} - Wave {}", run.format_time(), run.wave);
+                Self::create_credit(
+                    context,
+                    screen_size,
+                    &title,
+                    None,
+                    &mut all_credits,
+                    &mut credit_y,
+                )
+            });
+            Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+        }
+        if let Some(team_result) = Self::get_team_result(team_scores) {
+            Self::create_credit(
+                context,
+                screen_size,
+                &team_result,
+                Some(75.0),
+                &mut all_credits,
+                &mut credit_y,
+            );
+            for (team, score) in team_scores {
+                let title = format!("{} Team - {}", team.title(), score);
+                Self::create_credit(
+                    context,
+                    screen_size,
+                    &title,
+                    None,
+                    &mut all_credits,
+                    &mut credit_y,
+                );
+            }
+            Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+        }
         Self::create_credit(
             context,
             screen_size,
@@ -77,6 +157,56 @@ impl Credits {
             )
         });
 
+        if !session_leaderboard.is_empty() {
+            Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+            Self::create_credit(
+                context,
+                screen_size,
+                "This Session",
+                None,
+                &mut all_credits,
+                &mut credit_y,
+            );
+            session_leaderboard
+                .iter()
+                .take(SESSION_LEADERBOARD_SIZE)
+                .for_each(|(username, score)| {
+                    let title = format!("{} - {}", username, score);
+                    Self::create_credit(
+                        context,
+                        screen_size,
+                        &title,
+                        None,
+                        &mut all_credits,
+                        &mut credit_y,
+                    )
+                });
+            Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+        }
+
+        let regulars = Self::get_regulars(scores, chatter_stats);
+        if !regulars.is_empty() {
+            Self::create_credit(
+                context,
+                screen_size,
+                "Thanks For Coming Back",
+                None,
+                &mut all_credits,
+                &mut credit_y,
+            );
+            regulars.iter().for_each(|regular| {
+                Self::create_credit(
+                    context,
+                    screen_size,
+                    regular,
+                    None,
+                    &mut all_credits,
+                    &mut credit_y,
+                )
+            });
+            Self::create_space(context, screen_size, &mut all_credits, &mut credit_y);
+        }
+
         Self::create_credit(
             context,
             screen_size,
@@ -113,11 +243,52 @@ impl Credits {
         Ok(Credits { all_credits })
     }
 
-    fn get_random_message(file_name: &str) -> String {
-        let mut rng = thread_rng();
+    /// `None` outside of team mode
+    fn get_team_result(team_scores: &[(Team, u128)]) -> Option<String> {
+        match team_scores {
+            [(winner, winning_score), (_loser, losing_score)] if winning_score > losing_score => {
+                Some(format!("The {} Team Wins!", winner.title()))
+            }
+            [_, _] => Some("The Teams Tied!".to_owned()),
+            _ => None,
+        }
+    }
+
+    /// Shout-outs for the chatters in this round who have played the most rounds before
+    fn get_regulars(
+        scores: &HashMap<String, u128>,
+        chatter_stats: &HashMap<String, ChatterStats>,
+    ) -> Vec<String> {
+        let mut regulars: Vec<(&String, &ChatterStats)> = scores
+            .keys()
+            .filter_map(|username| Some((username, chatter_stats.get(username)?)))
+            .filter(|(_, stats)| stats.rounds_played > 1)
+            .collect();
+        regulars.sort_by(|a, b| {
+            b.1.rounds_played
+                .cmp(&a.1.rounds_played)
+                .then_with(|| a.0.cmp(b.0))
+        });
+        regulars
+            .into_iter()
+            .take(REGULARS_SIZE)
+            .map(|(username, stats)| {
+                let mut shout_out = format!(
+                    "{} - {} rounds, {} hits",
+                    username, stats.rounds_played, stats.hits_landed
+                );
+                if let Some(favorite_drop) = stats.favorite_drop() {
+                    shout_out.push_str(&format!(", loves {}s", favorite_drop));
+                }
+                shout_out
+            })
+            .collect()
+    }
+
+    fn get_random_message(file_name: &str, rng: &mut impl Rng) -> String {
         if let Some(messages) = utilities::load_messages(file_name) {
             messages
-                .choose(&mut rng)
+                .choose(rng)
                 .unwrap_or_else(|| DEFAULT_STREAMER_WIN_MESSAGE.to_owned())
         } else {
             DEFAULT_STREAMER_WIN_MESSAGE.to_owned()
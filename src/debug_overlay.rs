@@ -0,0 +1,100 @@
+use ggez::{
+    graphics::{self, Color, DrawMode, DrawParam, Font, MeshBuilder, Scale, Text},
+    nalgebra::Point2,
+    Context, GameResult,
+};
+
+use std::collections::VecDeque;
+
+use crate::game_event::GameEvent;
+use crate::hitbox::{HitArea, PlacedHitbox};
+use crate::interface::DROP_ZONE_HEIGHT;
+
+const LINE_HEIGHT: f32 = 24.0;
+/// How many of the latest game events are listed
+const RECENT_EVENTS: usize = 8;
+const HITBOX_COLOR: Color = Color::new(1.0, 0.0, 0.0, 1.0);
+/// Pixel masked hitboxes only collide with the solid pixels inside the outline
+const MASKED_HITBOX_COLOR: Color = Color::new(0.0, 1.0, 1.0, 1.0);
+
+/// Numbers for whoever is running the stream, toggled with F3
+#[derive(Debug, Default)]
+pub struct DebugOverlay {
+    visible: bool,
+    /// Kept while hidden too, so they are there as soon as it is shown
+    recent_events: VecDeque<String>,
+}
+
+impl DebugOverlay {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The streamer jumps all the time, so jumps are left out
+    pub fn record_event(&mut self, event: &GameEvent) {
+        if let GameEvent::PlayerJumped { .. } = event {
+            return;
+        }
+        if self.recent_events.len() == RECENT_EVENTS {
+            self.recent_events.pop_front();
+        }
+        self.recent_events.push_back(event.to_string());
+    }
+
+    /// Oldest first
+    pub fn recent_events(&self) -> impl Iterator<Item = &String> {
+        self.recent_events.iter()
+    }
+
+    /// Draws one line of text per entry in the top left corner of the arena
+    pub fn draw(&self, context: &mut Context, lines: &[String]) -> GameResult<()> {
+        for (index, line) in lines.iter().enumerate() {
+            let mut text = Text::new(line.as_str());
+            text.set_font(Font::default(), Scale::uniform(20.0));
+            graphics::draw(
+                context,
+                &text,
+                DrawParam::new()
+                    .dest(Point2::new(
+                        10.0,
+                        DROP_ZONE_HEIGHT + 10.0 + index as f32 * LINE_HEIGHT,
+                    ))
+                    .color(Color::new(1.0, 1.0, 0.0, 1.0)),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Outlines where everything can be hit
+    pub fn draw_hitboxes(
+        &self,
+        context: &mut Context,
+        hitboxes: &[PlacedHitbox],
+    ) -> GameResult<()> {
+        if hitboxes.is_empty() {
+            return Ok(());
+        }
+        let mut outlines = MeshBuilder::new();
+        for hitbox in hitboxes {
+            let color = if hitbox.has_mask() {
+                MASKED_HITBOX_COLOR
+            } else {
+                HITBOX_COLOR
+            };
+            match &hitbox.area {
+                HitArea::Circle { center, radius } => {
+                    outlines.circle(DrawMode::stroke(2.0), *center, *radius, 0.5, color);
+                }
+                HitArea::Polygon(corners) => {
+                    outlines.polygon(DrawMode::stroke(2.0), corners, color)?;
+                }
+            }
+        }
+        let outlines = outlines.build(context)?;
+        graphics::draw(context, &outlines, DrawParam::new())
+    }
+}
@@ -64,23 +64,13 @@ impl DrawSystem for GameObjectDrawSystem {
                 )),
             )?;
         }
-        // let border = MeshBuilder::new()
-        //     .rectangle(
-        //         DrawMode::stroke(2.0),
-        //         Rect::new(location.x, location.y, size.0, size.1),
-        //         Color::new(1.0, 0.0, 0.0, 1.0),
-        //     )
-        //     .build(context)?;
-        // graphics::draw(context, &border, DrawParam::new())?;
 
         Ok(())
     }
 
     fn get_size(&self) -> Option<(f32, f32)> {
-        if let Some(sprite) = &self.sprite {
-            Some((sprite.width * self.scale_by, sprite.height * self.scale_by))
-        } else {
-            None
-        }
+        self.sprite
+            .as_ref()
+            .map(|sprite| (sprite.width * self.scale_by, sprite.height * self.scale_by))
     }
 }
@@ -1,13 +1,18 @@
 use super::DrawSystem;
-use ggez::graphics::{Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect};
+use crate::interface::DROP_ZONE_HEIGHT;
+use ggez::graphics::{Color, DrawMode, DrawParam, Font, Mesh, MeshBuilder, Rect, Scale, Text};
 use ggez::nalgebra::Point2;
 use ggez::{graphics, Context, GameResult};
 
 const TIMER_WIDTH: f32 = 5.0;
+const LABEL_FONT_SIZE: f32 = 30.0;
+const LABEL_MARGIN: f32 = 10.0;
 
 #[derive(Debug)]
 pub struct TimerDrawSystem {
     timer: Mesh,
+    /// Drawn in the top right of the arena, it stays put while the timer runs down
+    label: Option<Text>,
     width: f32,
     height: f32,
 }
@@ -28,10 +33,18 @@ impl TimerDrawSystem {
 
         Ok(TimerDrawSystem {
             timer,
+            label: None,
             width: TIMER_WIDTH,
             height: screen_height,
         })
     }
+
+    pub fn with_label(mut self, label: &str) -> TimerDrawSystem {
+        let mut text = Text::new(label);
+        text.set_font(Font::default(), Scale::uniform(LABEL_FONT_SIZE));
+        self.label = Some(text);
+        self
+    }
 }
 
 impl DrawSystem for TimerDrawSystem {
@@ -43,7 +56,15 @@ impl DrawSystem for TimerDrawSystem {
         location: Point2<f32>,
         _rotation: &f32,
     ) -> GameResult<()> {
-        graphics::draw(context, &self.timer, DrawParam::new().dest(location))
+        graphics::draw(context, &self.timer, DrawParam::new().dest(location))?;
+        if let Some(label) = &self.label {
+            let label_location = Point2::new(
+                location.x - label.width(context) as f32 - LABEL_MARGIN,
+                DROP_ZONE_HEIGHT + LABEL_MARGIN,
+            );
+            graphics::draw(context, label, DrawParam::new().dest(label_location))?;
+        }
+        Ok(())
     }
 
     fn get_size(&self) -> Option<(f32, f32)> {
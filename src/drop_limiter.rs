@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use crate::command::Command;
+use crate::config::Config;
+use crate::game_object_type::GameObjectType;
+
+/// Why a drop wasn't let into the arena
+#[derive(Debug, Clone, PartialEq)]
+pub enum Refusal {
+    /// The chatter has to wait before dropping anything else
+    Cooldown { seconds_left: u64 },
+    /// The chatter has to wait before dropping this object again
+    ObjectCooldown { object: String, seconds_left: u64 },
+    /// There are too many enemies in the arena already
+    ArenaFull,
+}
+
+impl Refusal {
+    pub fn to_message(&self, chatter_name: &str) -> String {
+        match self {
+            Refusal::Cooldown { seconds_left } => format!(
+                "@{} slow down, you can drop again in {} seconds",
+                chatter_name, seconds_left
+            ),
+            Refusal::ObjectCooldown {
+                object,
+                seconds_left,
+            } => format!(
+                "@{} you can drop another {} in {} seconds",
+                chatter_name, object, seconds_left
+            ),
+            Refusal::ArenaFull => {
+                "The arena is full, wait for some of it to clear before dropping more".to_owned()
+            }
+        }
+    }
+}
+
+/// Keeps track of who dropped what when, all in simulation ticks so replays refuse the same drops
+#[derive(Debug, Default)]
+pub struct DropLimiter {
+    last_drop: HashMap<String, u64>,
+    last_object_drop: HashMap<(String, String), u64>,
+    last_refusal_reply: Option<u64>,
+}
+
+impl DropLimiter {
+    pub fn check(
+        &self,
+        command: &Command,
+        tick: u64,
+        live_enemies: usize,
+        config: &Config,
+    ) -> Result<(), Refusal> {
+        let limits = &config.limits;
+        let framerate_target = config.game.framerate_target as u64;
+        let ticks_left = |last_tick: Option<&u64>, cooldown_seconds: f32| {
+            let cooldown_ticks = config.game.seconds_to_ticks(cooldown_seconds);
+            last_tick
+                .map(|last_tick| {
+                    last_tick
+                        .saturating_add(cooldown_ticks)
+                        .saturating_sub(tick)
+                })
+                .unwrap_or(0)
+        };
+        // rounded up, "0 seconds" would be confusing
+        let to_seconds = |ticks: u64| ticks.div_ceil(framerate_target);
+
+        let drop_cooldown_seconds = if command.chatter.is_subscriber {
+            limits.subscriber_drop_cooldown_seconds
+        } else {
+            limits.drop_cooldown_seconds
+        };
+        let drop_ticks_left = ticks_left(
+            self.last_drop.get(&command.chatter.name),
+            drop_cooldown_seconds,
+        );
+        let object_ticks_left = ticks_left(
+            self.last_object_drop
+                .get(&(command.chatter.name.clone(), command.object.name.clone())),
+            command.object.cooldown_seconds,
+        );
+        if object_ticks_left > drop_ticks_left {
+            return Err(Refusal::ObjectCooldown {
+                object: command.object.name.clone(),
+                seconds_left: to_seconds(object_ticks_left),
+            });
+        }
+        if drop_ticks_left > 0 {
+            return Err(Refusal::Cooldown {
+                seconds_left: to_seconds(drop_ticks_left),
+            });
+        }
+
+        if command.object.get_game_object_type() == GameObjectType::Enemy
+            && live_enemies >= limits.max_live_enemies
+        {
+            return Err(Refusal::ArenaFull);
+        }
+
+        Ok(())
+    }
+
+    /// Starts the cooldowns for a drop that made it into the arena
+    pub fn record(&mut self, command: &Command, tick: u64) {
+        self.last_drop.insert(command.chatter.name.clone(), tick);
+        self.last_object_drop.insert(
+            (command.chatter.name.clone(), command.object.name.clone()),
+            tick,
+        );
+    }
+
+    /// Whether chat should be told about a refusal now, at most once per
+    /// `limits.refusal_reply_cooldown_seconds` no matter who was refused
+    pub fn should_reply(&mut self, tick: u64, config: &Config) -> bool {
+        if !config.limits.refusal_replies {
+            return false;
+        }
+        let cooldown_ticks = config
+            .game
+            .seconds_to_ticks(config.limits.refusal_reply_cooldown_seconds);
+        if let Some(last_refusal_reply) = self.last_refusal_reply {
+            if tick < last_refusal_reply.saturating_add(cooldown_ticks) {
+                return false;
+            }
+        }
+        self.last_refusal_reply = Some(tick);
+        true
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::chatter::Chatter;
+use crate::input_state::PlayerNumber;
+use crate::running_state::RunningState;
+
+/// Something that happened in the game, published by whatever noticed it
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// `dropped_by` is `None` for things the game spawns on its own, like the boss's spit
+    ObjectSpawned {
+        object_name: String,
+        dropped_by: Option<Chatter>,
+    },
+    /// `chatter` dropped what hit the streamer, `None` when nobody gets the credit
+    PlayerHit {
+        player_number: PlayerNumber,
+        chatter: Option<Chatter>,
+    },
+    /// Picked up a heart that `chatter` dropped
+    PlayerHealed {
+        player_number: PlayerNumber,
+        chatter: Option<Chatter>,
+    },
+    /// Caught a heart that `chatter` dropped with as many lives as allowed, so it took one away
+    PlayerHurtByHeart {
+        player_number: PlayerNumber,
+        chatter: Option<Chatter>,
+    },
+    PlayerJumped {
+        player_number: PlayerNumber,
+    },
+    /// The streamer landed on the boss, which doesn't always hurt it
+    BossStomped,
+    BossHurt {
+        health_left: u8,
+    },
+    /// `object_name` is `None` for the streamers
+    ObjectDied {
+        object_name: Option<String>,
+        chatter: Option<Chatter>,
+        reason: DeathReason,
+    },
+    RoundStarted {
+        round: u32,
+    },
+    /// `winner` is either `PlayerWon` or `ChatWon`
+    RoundEnded {
+        round: u32,
+        winner: RunningState,
+    },
+}
+
+/// Why a game object left the arena
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeathReason {
+    /// Its time was up, like fire burning out
+    TimeRanOut,
+    /// Bounced too many times
+    Broke,
+    LeftArena,
+    PickedUp,
+    OutOfLives,
+    Defeated,
+    /// Touched an object that gets rid of it in the catalog's interactions
+    Destroyed {
+        by: String,
+    },
+}
+
+/// Short enough for a line of the debug overlay
+impl fmt::Display for GameEvent {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameEvent::ObjectSpawned {
+                object_name,
+                dropped_by: Some(chatter),
+            } => write!(formatter, "{} dropped by {}", object_name, chatter.name),
+            GameEvent::ObjectSpawned { object_name, .. } => {
+                write!(formatter, "{} spawned", object_name)
+            }
+            GameEvent::PlayerHit {
+                player_number,
+                chatter: Some(chatter),
+            } => write!(
+                formatter,
+                "streamer {:?} hit by {}",
+                player_number, chatter.name
+            ),
+            GameEvent::PlayerHit { player_number, .. } => {
+                write!(formatter, "streamer {:?} hit", player_number)
+            }
+            GameEvent::PlayerHealed { player_number, .. } => {
+                write!(formatter, "streamer {:?} healed", player_number)
+            }
+            GameEvent::PlayerHurtByHeart { player_number, .. } => {
+                write!(formatter, "streamer {:?} hurt by a heart", player_number)
+            }
+            GameEvent::PlayerJumped { player_number } => {
+                write!(formatter, "streamer {:?} jumped", player_number)
+            }
+            GameEvent::BossStomped => write!(formatter, "boss stomped"),
+            GameEvent::BossHurt { health_left } => {
+                write!(formatter, "boss hurt, {} health left", health_left)
+            }
+            GameEvent::ObjectDied {
+                object_name,
+                reason,
+                ..
+            } => write!(
+                formatter,
+                "{} died: {}",
+                object_name.as_deref().unwrap_or("streamer"),
+                reason
+            ),
+            GameEvent::RoundStarted { round } => write!(formatter, "round {} started", round),
+            GameEvent::RoundEnded { round, winner } => {
+                write!(formatter, "round {} ended, {:?}", round, winner)
+            }
+        }
+    }
+}
+
+impl fmt::Display for DeathReason {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeathReason::TimeRanOut => write!(formatter, "time ran out"),
+            DeathReason::Broke => write!(formatter, "broke"),
+            DeathReason::LeftArena => write!(formatter, "left the arena"),
+            DeathReason::PickedUp => write!(formatter, "picked up"),
+            DeathReason::OutOfLives => write!(formatter, "out of lives"),
+            DeathReason::Defeated => write!(formatter, "defeated"),
+            DeathReason::Destroyed { by } => write!(formatter, "destroyed by {}", by),
+        }
+    }
+}
+
+/// Queues events as they are published and hands them to every subscriber when they are
+/// dispatched, so that everything sees them in the same order
+#[derive(Debug)]
+pub struct EventBus {
+    publish: Sender<GameEvent>,
+    published: Receiver<GameEvent>,
+    subscribers: Vec<Sender<GameEvent>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (publish, published) = channel();
+        EventBus {
+            publish,
+            published,
+            subscribers: vec![],
+        }
+    }
+}
+
+impl EventBus {
+    /// For systems that publish on their own, like the streamer's physics
+    pub fn publisher(&self) -> Sender<GameEvent> {
+        self.publish.clone()
+    }
+
+    pub fn publish(&self, event: GameEvent) {
+        // the bus holds on to its own receiver, so this can't fail
+        self.publish.send(event).ok();
+    }
+
+    /// Gets every event dispatched from now on, subscribers that hang up are forgotten
+    pub fn subscribe(&mut self) -> Receiver<GameEvent> {
+        let (subscriber, events) = channel();
+        self.subscribers.push(subscriber);
+        events
+    }
+
+    /// Everything published since the last dispatch, in order, which is also sent to the
+    /// subscribers
+    pub fn dispatch(&mut self) -> Vec<GameEvent> {
+        let events: Vec<GameEvent> = self.published.try_iter().collect();
+        self.subscribers.retain(|subscriber| {
+            events
+                .iter()
+                .all(|event| subscriber.send(event.clone()).is_ok())
+        });
+        events
+    }
+}
@@ -1,11 +1,13 @@
 use super::{Chatter, DrawSystem, GameObjectType, LifeSystem, PhysicsSystem};
+use crate::broadphase::Broadphase;
+use crate::game_event::DeathReason;
+use crate::hitbox::{Hitbox, PlacedHitbox};
+use crate::input_state::{InputState, PlayerNumber};
 use eyre::Result;
 use ggez::graphics::Rect;
 use ggez::nalgebra::Point2;
 use ggez::{Context, GameResult};
 
-const GRAVITY_FORCE: f32 = 0.3;
-
 #[derive(Debug)]
 pub struct GameObject {
     pub location: Rect,
@@ -16,10 +18,17 @@ pub struct GameObject {
     pub chatter: Option<Chatter>,
     rotation: f32,
     pub my_type: GameObjectType,
+    pub object_name: Option<String>,
+    /// Which streamer character this is, `None` for everything else
+    pub player_number: Option<PlayerNumber>,
+    /// The part of the sprite that collides, the whole sprite unless the catalog says otherwise
+    pub hitbox: Hitbox,
+    /// The name of what got rid of it, when it met something in the arena that does that
+    destroyed_by: Option<String>,
 }
 
 impl GameObject {
-    #[allow(clippy::clippy::too_many_arguments)]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         x: f32,
         y: f32,
@@ -41,23 +50,32 @@ impl GameObject {
             chatter,
             rotation: 0.0,
             my_type,
+            object_name: None,
+            player_number: None,
+            hitbox: Hitbox::default(),
+            destroyed_by: None,
         }
     }
 
+    /// `dt` is the length of the tick in seconds
     pub fn update(
         &mut self,
         time_since_start: std::time::Duration,
+        dt: f32,
         screen_size: (f32, f32),
-        context: &mut Context,
-        collidable_game_objects: &[GameObject],
+        gravity_force: f32,
+        input: &InputState,
+        broadphase: &Broadphase,
     ) -> Result<()> {
         if let Some(physics_system) = &mut self.physics_system {
             physics_system.update(
+                dt,
                 &mut self.location,
                 screen_size,
-                GRAVITY_FORCE,
-                context,
-                collidable_game_objects,
+                gravity_force,
+                input,
+                broadphase,
+                &self.hitbox,
                 &mut self.rotation,
                 &mut self.life_system,
             )?;
@@ -91,21 +109,57 @@ impl GameObject {
         Ok(())
     }
 
+    /// Where the object can be hit right now
+    pub fn placed_hitbox(&self) -> PlacedHitbox {
+        self.hitbox.place(self.location, self.rotation)
+    }
+
+    pub fn has_draw_system(&self) -> bool {
+        self.draw_system.is_some()
+    }
+
+    pub fn set_draw_system(&mut self, draw_system: Box<dyn DrawSystem>) {
+        self.draw_system = Some(draw_system);
+    }
+
     pub fn is_alive(&self) -> bool {
-        if let Some(life_system) = &self.life_system {
+        if self.destroyed_by.is_some() {
+            false
+        } else if let Some(life_system) = &self.life_system {
             life_system.is_alive()
         } else {
             true
         }
     }
 
-    pub fn get_lives_left(&self) -> Option<u8> {
-        if let Some(life_system) = &self.life_system {
-            Some(life_system.get_lives_left())
-        } else {
-            None
+    /// Gone at the end of the tick, whatever its life system says
+    pub fn destroy(&mut self, by: &str) {
+        self.destroyed_by = Some(by.to_owned());
+    }
+
+    /// Why the object is no longer alive, only objects with a life system die on their own
+    pub fn death_reason(&self) -> Option<DeathReason> {
+        match &self.destroyed_by {
+            Some(by) => Some(DeathReason::Destroyed { by: by.clone() }),
+            None => self
+                .life_system
+                .as_ref()
+                .map(|life_system| life_system.death_reason()),
         }
     }
+
+    /// Head away from something at `other`
+    pub fn bounce_off(&mut self, other: &Rect) {
+        if let Some(physics_system) = &mut self.physics_system {
+            physics_system.bounce_off(&self.location, other);
+        }
+    }
+
+    pub fn get_lives_left(&self) -> Option<u8> {
+        self.life_system
+            .as_ref()
+            .map(|life_system| life_system.get_lives_left())
+    }
 }
 
 impl Clone for GameObject {
@@ -118,6 +172,10 @@ impl Clone for GameObject {
             chatter: self.chatter.clone(),
             rotation: self.rotation,
             my_type: self.my_type.clone(),
+            object_name: self.object_name.clone(),
+            player_number: self.player_number,
+            hitbox: self.hitbox.clone(),
+            destroyed_by: self.destroyed_by.clone(),
             life_system: None,
         }
     }
@@ -1,7 +1,9 @@
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum GameObjectType {
     Player,
     Enemy,
     Interface,
     Heart,
+    /// Steered by chat in boss mode, the streamer can hurt it by landing on it
+    Boss,
 }
@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use eyre::{bail, eyre, Result};
+use ggez::{filesystem, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::utilities;
+
+/// Lives in the ggez user config directory, next to the game config
+pub const HIGH_SCORES_FILE_NAME: &str = "high_scores.json";
+/// The `name:score` lines older versions of the game wrote
+const LEGACY_FILE_NAME: &str = "high_scores";
+/// What the legacy file is renamed to once it has been migrated, so it's only migrated once
+const MIGRATED_LEGACY_FILE_NAME: &str = "high_scores.legacy";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HighScoresFile {
+    version: u32,
+    /// Sorted so the file diffs nicely
+    scores: BTreeMap<String, u128>,
+}
+
+/// High scores kept between games. Saving writes a temp file and renames it over the old one,
+/// so a crash mid-save can't lose them, and the previous file is kept as a backup.
+#[derive(Debug, Clone)]
+pub struct HighScoreStore {
+    directory: PathBuf,
+}
+
+/// The scores that could be read, along with everything that was wrong with the files
+#[derive(Debug, Default)]
+pub struct LoadedHighScores {
+    pub scores: HashMap<String, u128>,
+    pub problems: Vec<String>,
+}
+
+impl HighScoreStore {
+    pub fn new(directory: impl Into<PathBuf>) -> HighScoreStore {
+        HighScoreStore {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn in_user_config_dir(context: &Context) -> HighScoreStore {
+        HighScoreStore::new(filesystem::user_config_dir(context))
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(HIGH_SCORES_FILE_NAME)
+    }
+
+    pub fn backup_path(&self) -> PathBuf {
+        utilities::backup_path(&self.path())
+    }
+
+    /// Never fails, anything that can't be read is reported in `problems` and left out
+    pub fn load(&self) -> LoadedHighScores {
+        let mut problems = vec![];
+        let path = self.path();
+        if !path.exists() {
+            return self.migrate_legacy_scores();
+        }
+
+        let scores = utilities::read_with_backup(&path, parse_scores_file, &mut problems)
+            .unwrap_or_default();
+        LoadedHighScores { scores, problems }
+    }
+
+    pub fn save(&self, scores: &HashMap<String, u128>) -> Result<()> {
+        let high_scores_file = HighScoresFile {
+            version: FORMAT_VERSION,
+            scores: scores
+                .iter()
+                .map(|(name, score)| (name.clone(), *score))
+                .collect(),
+        };
+        utilities::write_atomically(
+            &self.path(),
+            &serde_json::to_string_pretty(&high_scores_file)?,
+        )
+    }
+
+    fn migrate_legacy_scores(&self) -> LoadedHighScores {
+        let legacy_path = self.directory.join(LEGACY_FILE_NAME);
+        let raw_scores = match fs::read_to_string(&legacy_path) {
+            Ok(raw_scores) => raw_scores,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return LoadedHighScores::default()
+            }
+            Err(error) => {
+                return LoadedHighScores {
+                    scores: HashMap::new(),
+                    problems: vec![format!("{:?}: {}", legacy_path, error)],
+                }
+            }
+        };
+
+        let mut loaded = parse_legacy_scores(&raw_scores);
+        for problem in &mut loaded.problems {
+            *problem = format!("{:?}: {}", legacy_path, problem);
+        }
+
+        let migrated = self.save(&loaded.scores).and_then(|_| {
+            fs::rename(&legacy_path, self.directory.join(MIGRATED_LEGACY_FILE_NAME))?;
+            Ok(())
+        });
+        if let Err(error) = migrated {
+            loaded.problems.push(format!(
+                "couldn't migrate {:?} to {:?}: {}",
+                legacy_path,
+                self.path(),
+                error
+            ));
+        }
+        loaded
+    }
+}
+
+fn parse_scores_file(raw_scores: &str) -> Result<HashMap<String, u128>> {
+    let high_scores_file: HighScoresFile = serde_json::from_str(raw_scores)?;
+    if high_scores_file.version > FORMAT_VERSION {
+        bail!(
+            "written by a newer version of the game (format version {})",
+            high_scores_file.version
+        );
+    }
+    Ok(high_scores_file.scores.into_iter().collect())
+}
+
+/// One `name:score` per line, lines that don't look like that are reported and skipped
+pub fn parse_legacy_scores(raw_scores: &str) -> LoadedHighScores {
+    let mut loaded = LoadedHighScores::default();
+    for (index, line) in raw_scores.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match parse_legacy_line(line) {
+            Ok((name, score)) => {
+                let high_score = loaded.scores.entry(name.to_owned()).or_insert(0);
+                *high_score = (*high_score).max(score);
+            }
+            Err(error) => {
+                loaded
+                    .problems
+                    .push(format!("line {}: {} in `{}`", index + 1, error, line))
+            }
+        }
+    }
+    loaded
+}
+
+fn parse_legacy_line(line: &str) -> Result<(&str, u128)> {
+    let (name, score) = line
+        .rsplit_once(':')
+        .ok_or_else(|| eyre!("missing the `:` between name and score"))?;
+    if name.is_empty() {
+        bail!("missing the name");
+    }
+    let score = score
+        .parse()
+        .map_err(|_| eyre!("the score has to be a whole number"))?;
+    Ok((name, score))
+}
@@ -0,0 +1,372 @@
+use std::sync::Arc;
+
+use ggez::graphics::Rect;
+use ggez::nalgebra::{Point2, Vector2};
+use serde::{Deserialize, Serialize};
+
+/// `Sprite::draw` flips sprites upside down past this rotation, rotated hitboxes flip with them
+const MIRRORED_ROTATION: f32 = 3.0;
+/// Pixels that are at least half see-through can't be hit
+const SOLID_ALPHA: u8 = 128;
+
+/// Where an object can be hit, in fractions of its sprite so that it fits at any scale
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "snake_case", deny_unknown_fields)]
+pub enum HitboxShape {
+    /// The whole sprite
+    #[default]
+    Bounds,
+    /// The sprite with its sides pulled in, `left = 0.25` leaves out the left quarter.
+    /// Stays upright when the sprite rotates.
+    Inset {
+        #[serde(default)]
+        left: f32,
+        #[serde(default)]
+        right: f32,
+        #[serde(default)]
+        top: f32,
+        #[serde(default)]
+        bottom: f32,
+    },
+    /// A circle in the middle of the sprite, `radius` is a fraction of the sprite width
+    Circle { radius: f32 },
+    /// A box in the middle of the sprite, turned by `angle` degrees, that rotates with the sprite.
+    /// `width` and `height` are fractions of the sprite's width and height.
+    Oriented { width: f32, height: f32, angle: f32 },
+}
+
+/// Which pixels of a sprite are solid. The simulation doesn't know which frame is being drawn,
+/// so a pixel is solid when it is solid in any frame of the sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelMask {
+    width: usize,
+    height: usize,
+    solid: Vec<bool>,
+}
+
+impl PixelMask {
+    /// `rgba` is the whole sprite sheet, four bytes per pixel like `Image::to_rgba8` gives
+    pub fn from_rgba8(
+        sheet_width: u16,
+        sheet_height: u16,
+        rgba: &[u8],
+        frames_across: u16,
+        frames_down: u16,
+    ) -> PixelMask {
+        let sheet_width = sheet_width as usize;
+        let width = sheet_width / frames_across.max(1) as usize;
+        let height = sheet_height as usize / frames_down.max(1) as usize;
+        let mut solid = vec![false; width * height];
+        for (index, pixel) in rgba.chunks_exact(4).enumerate() {
+            if pixel[3] < SOLID_ALPHA {
+                continue;
+            }
+            let x = index % sheet_width % width.max(1);
+            let y = index / sheet_width % height.max(1);
+            if let Some(solid) = solid.get_mut(y * width + x) {
+                *solid = true;
+            }
+        }
+        PixelMask {
+            width,
+            height,
+            solid,
+        }
+    }
+
+    /// `x` and `y` are fractions of the frame, from its top left corner
+    fn is_solid(&self, x: f32, y: f32) -> bool {
+        if !(0.0..1.0).contains(&x) || !(0.0..1.0).contains(&y) {
+            return false;
+        }
+        let column = (x * self.width as f32) as usize;
+        let row = (y * self.height as f32) as usize;
+        self.solid[row * self.width + column]
+    }
+}
+
+/// An object's hitbox before it is put wherever the object is
+#[derive(Debug, Clone, Default)]
+pub struct Hitbox {
+    pub shape: HitboxShape,
+    /// Only objects with `pixel_mask` in the catalog get one, and only when their sprite is loaded
+    pub mask: Option<Arc<PixelMask>>,
+}
+
+impl Hitbox {
+    pub fn new(shape: HitboxShape) -> Hitbox {
+        Hitbox { shape, mask: None }
+    }
+
+    /// Where this hitbox is for an object drawn at `location`, turned by `rotation` radians
+    pub fn place(&self, location: Rect, rotation: f32) -> PlacedHitbox {
+        let sprite = SpriteTransform::new(location, rotation);
+        let area = match self.shape {
+            HitboxShape::Bounds => HitArea::from_rect(location),
+            HitboxShape::Inset {
+                left,
+                right,
+                top,
+                bottom,
+            } => HitArea::from_rect(Rect::new(
+                location.x + location.w * left,
+                location.y + location.h * top,
+                location.w * (1.0 - left - right),
+                location.h * (1.0 - top - bottom),
+            )),
+            HitboxShape::Circle { radius } => HitArea::Circle {
+                center: sprite.center,
+                radius: radius * location.w,
+            },
+            HitboxShape::Oriented {
+                width,
+                height,
+                angle,
+            } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let half_width = width * location.w / 2.0;
+                let half_height = height * location.h / 2.0;
+                let corner = |x: f32, y: f32| {
+                    let x = x * half_width;
+                    let y = y * half_height;
+                    sprite.world_point(Vector2::new(x * cos - y * sin, x * sin + y * cos))
+                };
+                HitArea::Polygon([
+                    corner(-1.0, -1.0),
+                    corner(1.0, -1.0),
+                    corner(1.0, 1.0),
+                    corner(-1.0, 1.0),
+                ])
+            }
+        };
+        PlacedHitbox {
+            bounds: area.bounds(),
+            area,
+            mask: self.mask.clone().map(|mask| (mask, sprite)),
+        }
+    }
+}
+
+/// A hitbox where its object is this tick
+#[derive(Debug, Clone)]
+pub struct PlacedHitbox {
+    pub area: HitArea,
+    bounds: Rect,
+    mask: Option<(Arc<PixelMask>, SpriteTransform)>,
+}
+
+/// The shape of a placed hitbox in arena coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub enum HitArea {
+    Circle {
+        center: Point2<f32>,
+        radius: f32,
+    },
+    /// Corners in order around the box
+    Polygon([Point2<f32>; 4]),
+}
+
+impl PlacedHitbox {
+    /// The smallest upright rectangle around the hitbox
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn has_mask(&self) -> bool {
+        self.mask.is_some()
+    }
+
+    /// Touching counts, like it does for `Rect::overlaps`
+    pub fn overlaps(&self, other: &PlacedHitbox) -> bool {
+        if !self.bounds.overlaps(&other.bounds) || !self.area.overlaps(&other.area) {
+            return false;
+        }
+        match (&self.mask, &other.mask) {
+            (Some((mask, sprite)), _) => self.any_solid_pixel_in(mask, sprite, other),
+            (None, Some((mask, sprite))) => other.any_solid_pixel_in(mask, sprite, self),
+            (None, None) => true,
+        }
+    }
+
+    pub fn contains(&self, point: Point2<f32>) -> bool {
+        self.area.contains(point)
+            && self
+                .mask
+                .as_ref()
+                .is_none_or(|(mask, sprite)| sprite.is_solid(mask, point))
+    }
+
+    /// Checks the middle of every solid pixel, which is plenty for pixel art
+    fn any_solid_pixel_in(
+        &self,
+        mask: &PixelMask,
+        sprite: &SpriteTransform,
+        other: &PlacedHitbox,
+    ) -> bool {
+        (0..mask.height).any(|row| {
+            (0..mask.width).any(|column| {
+                if !mask.solid[row * mask.width + column] {
+                    return false;
+                }
+                let point = sprite.world_point(Vector2::new(
+                    ((column as f32 + 0.5) / mask.width as f32 - 0.5) * sprite.size.x,
+                    ((row as f32 + 0.5) / mask.height as f32 - 0.5) * sprite.size.y,
+                ));
+                self.area.contains(point) && other.contains(point)
+            })
+        })
+    }
+}
+
+impl HitArea {
+    fn from_rect(rect: Rect) -> HitArea {
+        HitArea::Polygon([
+            Point2::new(rect.left(), rect.top()),
+            Point2::new(rect.right(), rect.top()),
+            Point2::new(rect.right(), rect.bottom()),
+            Point2::new(rect.left(), rect.bottom()),
+        ])
+    }
+
+    fn bounds(&self) -> Rect {
+        match self {
+            HitArea::Circle { center, radius } => Rect::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            ),
+            HitArea::Polygon(corners) => {
+                let left = corners
+                    .iter()
+                    .map(|corner| corner.x)
+                    .fold(f32::MAX, f32::min);
+                let right = corners
+                    .iter()
+                    .map(|corner| corner.x)
+                    .fold(f32::MIN, f32::max);
+                let top = corners
+                    .iter()
+                    .map(|corner| corner.y)
+                    .fold(f32::MAX, f32::min);
+                let bottom = corners
+                    .iter()
+                    .map(|corner| corner.y)
+                    .fold(f32::MIN, f32::max);
+                Rect::new(left, top, right - left, bottom - top)
+            }
+        }
+    }
+
+    fn overlaps(&self, other: &HitArea) -> bool {
+        match (self, other) {
+            (
+                HitArea::Circle { center, radius },
+                HitArea::Circle {
+                    center: other_center,
+                    radius: other_radius,
+                },
+            ) => (center - other_center).norm() <= radius + other_radius,
+            (HitArea::Circle { center, radius }, HitArea::Polygon(corners))
+            | (HitArea::Polygon(corners), HitArea::Circle { center, radius }) => {
+                polygon_contains(corners, *center)
+                    || edges(corners)
+                        .any(|(start, end)| distance_to_segment(*center, start, end) <= *radius)
+            }
+            (HitArea::Polygon(corners), HitArea::Polygon(other_corners)) => {
+                // separating axis test, boxes only need the normals of their edges
+                edges(corners)
+                    .chain(edges(other_corners))
+                    .all(|(start, end)| {
+                        let axis = Vector2::new(start.y - end.y, end.x - start.x);
+                        let (min, max) = project(corners, &axis);
+                        let (other_min, other_max) = project(other_corners, &axis);
+                        max >= other_min && other_max >= min
+                    })
+            }
+        }
+    }
+
+    fn contains(&self, point: Point2<f32>) -> bool {
+        match self {
+            HitArea::Circle { center, radius } => (point - center).norm() <= *radius,
+            HitArea::Polygon(corners) => polygon_contains(corners, point),
+        }
+    }
+}
+
+/// How a sprite is drawn, turned around its middle and flipped like `Sprite::draw` does
+#[derive(Debug, Clone, Copy)]
+struct SpriteTransform {
+    center: Point2<f32>,
+    size: Vector2<f32>,
+    sin: f32,
+    cos: f32,
+    flip_y: f32,
+}
+
+impl SpriteTransform {
+    fn new(location: Rect, rotation: f32) -> SpriteTransform {
+        let (sin, cos) = rotation.sin_cos();
+        SpriteTransform {
+            center: Point2::new(location.x + location.w / 2.0, location.y + location.h / 2.0),
+            size: Vector2::new(location.w, location.h),
+            sin,
+            cos,
+            flip_y: if rotation > MIRRORED_ROTATION {
+                -1.0
+            } else {
+                1.0
+            },
+        }
+    }
+
+    /// `offset` is from the middle of the sprite, before it is flipped and turned
+    fn world_point(&self, offset: Vector2<f32>) -> Point2<f32> {
+        let y = offset.y * self.flip_y;
+        self.center
+            + Vector2::new(
+                offset.x * self.cos - y * self.sin,
+                offset.x * self.sin + y * self.cos,
+            )
+    }
+
+    fn is_solid(&self, mask: &PixelMask, point: Point2<f32>) -> bool {
+        let offset = point - self.center;
+        let x = offset.x * self.cos + offset.y * self.sin;
+        let y = (-offset.x * self.sin + offset.y * self.cos) * self.flip_y;
+        mask.is_solid(x / self.size.x + 0.5, y / self.size.y + 0.5)
+    }
+}
+
+fn edges(corners: &[Point2<f32>; 4]) -> impl Iterator<Item = (Point2<f32>, Point2<f32>)> + '_ {
+    (0..corners.len()).map(move |index| (corners[index], corners[(index + 1) % corners.len()]))
+}
+
+fn project(corners: &[Point2<f32>; 4], axis: &Vector2<f32>) -> (f32, f32) {
+    corners
+        .iter()
+        .map(|corner| corner.coords.dot(axis))
+        .fold((f32::MAX, f32::MIN), |(min, max), projected| {
+            (min.min(projected), max.max(projected))
+        })
+}
+
+/// Works whichever way around the corners go, flipped boxes go the other way
+fn polygon_contains(corners: &[Point2<f32>; 4], point: Point2<f32>) -> bool {
+    let sides: Vec<f32> = edges(corners)
+        .map(|(start, end)| (end - start).perp(&(point - start)))
+        .collect();
+    sides.iter().all(|side| *side >= 0.0) || sides.iter().all(|side| *side <= 0.0)
+}
+
+fn distance_to_segment(point: Point2<f32>, start: Point2<f32>, end: Point2<f32>) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.norm_squared();
+    let along = if length_squared == 0.0 {
+        0.0
+    } else {
+        ((point - start).dot(&segment) / length_squared).clamp(0.0, 1.0)
+    };
+    (point - (start + segment * along)).norm()
+}
@@ -0,0 +1,72 @@
+use ggez::input::keyboard::{self, KeyCode};
+use ggez::Context;
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the streamer's controls, taken once per simulation tick
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputState {
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+    /// Skips the splash screen or the intermission between rounds
+    #[serde(default)]
+    pub start_next_round: bool,
+    /// The arrow keys and Enter, for the second streamer in co-op
+    #[serde(default, skip_serializing_if = "PlayerInput::is_idle")]
+    pub player_two: PlayerInput,
+}
+
+/// The controls of one of the streamer characters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+}
+
+/// Which of the streamer characters something is for, the second one only plays in co-op
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerNumber {
+    One,
+    Two,
+}
+
+impl PlayerNumber {
+    pub const ALL: [PlayerNumber; 2] = [PlayerNumber::One, PlayerNumber::Two];
+}
+
+impl InputState {
+    pub fn from_keyboard(context: &Context) -> InputState {
+        InputState {
+            left: keyboard::is_key_pressed(context, KeyCode::A),
+            right: keyboard::is_key_pressed(context, KeyCode::S)
+                || keyboard::is_key_pressed(context, KeyCode::D),
+            jump: keyboard::is_key_pressed(context, KeyCode::Space),
+            start_next_round: keyboard::is_key_pressed(context, KeyCode::N),
+            player_two: PlayerInput {
+                left: keyboard::is_key_pressed(context, KeyCode::Left),
+                right: keyboard::is_key_pressed(context, KeyCode::Right),
+                jump: keyboard::is_key_pressed(context, KeyCode::Return)
+                    || keyboard::is_key_pressed(context, KeyCode::NumpadEnter),
+            },
+        }
+    }
+
+    pub fn for_player(&self, player_number: PlayerNumber) -> PlayerInput {
+        match player_number {
+            PlayerNumber::One => PlayerInput {
+                left: self.left,
+                right: self.right,
+                jump: self.jump,
+            },
+            PlayerNumber::Two => self.player_two,
+        }
+    }
+}
+
+impl PlayerInput {
+    /// Nothing is being pressed, which keeps replays of single player games small
+    pub fn is_idle(&self) -> bool {
+        *self == PlayerInput::default()
+    }
+}
@@ -1,4 +1,6 @@
-use crate::running_state::RunningState;
+use crate::{
+    broadphase::Broadphase, input_state::InputState, running_state::RunningState, vote::VoteTally,
+};
 
 use super::GameObject;
 use eyre::Result;
@@ -6,11 +8,18 @@ use ggez::graphics::{
     Align, Color, DrawMode, DrawParam, Font, Mesh, MeshBuilder, Rect, Scale, Text,
 };
 use ggez::nalgebra::Point2;
-use ggez::{graphics, timer, Context, GameResult};
+use ggez::{graphics, Context, GameResult};
 use graphics::Image;
+use std::time::Duration;
 
-const DROP_ZONE_HEIGHT: f32 = 50.0;
+pub const DROP_ZONE_HEIGHT: f32 = 50.0;
 const GAME_OVER_FONT_SIZE: f32 = 150.0;
+/// The part of the sidebar with the drop commands, covered by the tally in voting mode
+const VOTE_PANEL: (f32, f32) = (215.0, 750.0);
+const VOTE_LINE_HEIGHT: f32 = 40.0;
+const BOSS_HEALTH_BAR_HEIGHT: f32 = 20.0;
+/// How many objects and columns the tally lists
+const VOTE_TALLY_SIZE: usize = 5;
 
 pub struct Interface {
     pub width: f32,
@@ -22,25 +31,28 @@ pub struct Interface {
     game_objects: Vec<GameObject>,
     instruction_image: Image,
     heart_image: Image,
-    player_lives_left: u8,
+    /// One row of hearts for each streamer, the first streamer's row is at the bottom
+    player_lives_left: Vec<u8>,
     full_mask: Mesh,
+    vote_panel: Mesh,
 }
 
 impl Interface {
     pub fn new(
         context: &mut Context,
         screen_size: (f32, f32),
-        player_lives_left: u8,
+        player_lives_left: Vec<u8>,
+        drop_zone_count: u8,
     ) -> GameResult<Interface> {
         let instruction_image = Image::new(context, "/sidebar.png")?;
         let width = instruction_image.width().into();
         let (screen_width, screen_height) = screen_size;
         let mut drop_zones = vec![];
         let drop_zone_width = screen_width - width;
-        let single_drop_zone_width = drop_zone_width / crate::DROP_ZONE_COUNT as f32;
+        let single_drop_zone_width = drop_zone_width / drop_zone_count as f32;
         let mut drop_zone_labels = vec![];
 
-        for count in 0..crate::DROP_ZONE_COUNT {
+        for count in 0..drop_zone_count {
             let drop_zone = Rect::new(
                 count as f32 * single_drop_zone_width,
                 0.0,
@@ -75,6 +87,14 @@ impl Interface {
             )
             .build(context)?;
 
+        let vote_panel = MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                Rect::new(0.0, VOTE_PANEL.0, width, VOTE_PANEL.1),
+                Color::from_rgb(30, 48, 90),
+            )
+            .build(context)?;
+
         let mut attack_subtitle = Text::new("Attack the Streamer with following commands:");
         attack_subtitle.set_font(Font::default(), Scale::uniform(30.0));
         attack_subtitle.set_bounds(Point2::new(width, 60.0), Align::Center);
@@ -104,6 +124,7 @@ impl Interface {
             heart_image,
             player_lives_left,
             full_mask,
+            vote_panel,
         })
     }
     pub fn draw(
@@ -120,17 +141,21 @@ impl Interface {
             DrawParam::new().dest(Point2::new(screen_size.0 - self.width, 0.0)),
         )?;
 
-        let mut heart_x = screen_size.0
-            - (self.width / 2.0)
-            - ((self.heart_image.width() as f32) * self.player_lives_left as f32) / 2.0;
-        for _ in 0..self.player_lives_left {
-            graphics::draw(
-                context,
-                &self.heart_image,
-                DrawParam::new().dest(Point2::new(heart_x, 1015.0)),
-            )?;
+        let mut heart_y = 1015.0;
+        for lives_left in &self.player_lives_left {
+            let mut heart_x = screen_size.0
+                - (self.width / 2.0)
+                - ((self.heart_image.width() as f32) * *lives_left as f32) / 2.0;
+            for _ in 0..*lives_left {
+                graphics::draw(
+                    context,
+                    &self.heart_image,
+                    DrawParam::new().dest(Point2::new(heart_x, heart_y)),
+                )?;
 
-            heart_x += self.heart_image.width() as f32 + 5.0;
+                heart_x += self.heart_image.width() as f32 + 5.0;
+            }
+            heart_y -= self.heart_image.height() as f32 + 5.0;
         }
 
         for obj in &self.game_objects {
@@ -144,6 +169,89 @@ impl Interface {
         Ok(())
     }
 
+    /// Covers the drop commands in the sidebar with what chat is voting for
+    pub fn draw_vote_tally(
+        &self,
+        context: &mut Context,
+        screen_size: (f32, f32),
+        vote_tally: &VoteTally,
+        seconds_left: Option<u64>,
+    ) -> GameResult<()> {
+        let left = screen_size.0 - self.width;
+        graphics::draw(
+            context,
+            &self.vote_panel,
+            DrawParam::new().dest(Point2::new(left, 0.0)),
+        )?;
+
+        let mut lines = vec![
+            ("Chat Vote".to_owned(), 40.0),
+            ("#vote <object> <column>".to_owned(), 24.0),
+            match seconds_left {
+                Some(seconds_left) => (format!("{} seconds left", seconds_left), 24.0),
+                None => ("Waiting for the first vote".to_owned(), 24.0),
+            },
+        ];
+        lines.extend(
+            vote_tally
+                .object_counts()
+                .into_iter()
+                .take(VOTE_TALLY_SIZE)
+                .map(|(object, votes)| (format!("{} - {}", object, votes), 30.0)),
+        );
+        lines.extend(
+            vote_tally
+                .column_counts()
+                .into_iter()
+                .take(VOTE_TALLY_SIZE)
+                .map(|(column, votes)| (format!("column {} - {}", column, votes), 30.0)),
+        );
+
+        for (index, (line, size)) in lines.into_iter().enumerate() {
+            let mut text = Text::new(line);
+            text.set_font(Font::default(), Scale::uniform(size));
+            text.set_bounds(Point2::new(self.width, VOTE_LINE_HEIGHT), Align::Center);
+            graphics::draw(
+                context,
+                &text,
+                DrawParam::new().dest(Point2::new(
+                    left,
+                    VOTE_PANEL.0 + 20.0 + index as f32 * VOTE_LINE_HEIGHT,
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// A bar across the top of the arena, under the drop zones
+    pub fn draw_boss_health(
+        &self,
+        context: &mut Context,
+        screen_size: (f32, f32),
+        health: u8,
+        max_health: u8,
+    ) -> GameResult<()> {
+        let bar = Rect::new(
+            20.0,
+            DROP_ZONE_HEIGHT + 20.0,
+            screen_size.0 - self.width - 40.0,
+            BOSS_HEALTH_BAR_HEIGHT,
+        );
+        let health_left = health as f32 / max_health.max(1) as f32;
+        let mut mesh_builder = MeshBuilder::new();
+        if health > 0 {
+            mesh_builder.rectangle(
+                DrawMode::fill(),
+                Rect::new(bar.x, bar.y, bar.w * health_left, bar.h),
+                Color::from_rgb(200, 30, 30),
+            );
+        }
+        let mesh = mesh_builder
+            .rectangle(DrawMode::stroke(2.0), bar, graphics::WHITE)
+            .build(context)?;
+        graphics::draw(context, &mesh, DrawParam::new())
+    }
+
     fn draw_drop_zones(&self, context: &mut Context) -> GameResult<()> {
         self.drop_zones
             .iter()
@@ -173,32 +281,29 @@ impl Interface {
         Ok(())
     }
 
-    /// Take in an index like 3
-    /// which should return the middle x,y coordinates of the corresponding drop zone
-    pub fn get_column_coordinates_by_index(&self, index: u8) -> Point2<f32> {
-        Point2::new(
-            index as f32 * self.single_drop_zone_width + self.single_drop_zone_width / 2.0,
-            DROP_ZONE_HEIGHT / 2.0,
-        )
-    }
-
     pub fn add_game_object(&mut self, game_object: GameObject) {
         self.game_objects.push(game_object);
     }
 
-    pub fn update(&mut self, context: &mut Context, player_lives_left: u8) -> Result<()> {
-        let time_since_start = timer::time_since_start(context);
+    /// Removes the timers so that the next round can start its own
+    pub fn clear_game_objects(&mut self) {
+        self.game_objects.clear();
+    }
+
+    /// Moves the timers along by a tick of `dt` seconds
+    pub fn update(
+        &mut self,
+        time_since_start: Duration,
+        dt: f32,
+        player_lives_left: Vec<u8>,
+    ) -> Result<()> {
         let screen_size = self.screen_size;
-        let collidable_game_objects = vec![];
+        let broadphase = Broadphase::default();
+        let input = InputState::default();
         self.player_lives_left = player_lives_left;
 
         self.game_objects.iter_mut().try_for_each(|game_object| {
-            game_object.update(
-                time_since_start,
-                screen_size,
-                context,
-                &collidable_game_objects,
-            )
+            game_object.update(time_since_start, dt, screen_size, 0.0, &input, &broadphase)
         })?;
 
         Ok(())
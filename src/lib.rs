@@ -1,152 +1,340 @@
-mod chatter;
+pub mod boss;
+mod broadphase;
+pub mod chat_query;
+pub mod chat_queue;
+pub mod chat_transport;
+pub mod chatter;
+pub mod chatter_stats;
 pub mod command;
+pub mod config;
+pub mod control_command;
 mod credits;
+mod debug_overlay;
 mod draw_system;
+mod drop_limiter;
+pub mod game_event;
 mod game_object;
 mod game_object_type;
+pub mod high_scores;
+pub mod hitbox;
+pub mod input_state;
 mod interface;
 mod life_system;
+pub mod object_catalog;
+pub mod personal_bests;
 mod physics;
-mod running_state;
+pub mod replay;
+pub mod running_state;
+pub mod simulation;
+pub mod sound_effect;
 mod splash;
 mod sprites;
+pub mod team;
 mod utilities;
+pub mod vote;
 
+use chat_query::ChatQueries;
+use chat_queue::ChatQueue;
+use chat_transport::IncomingChat;
 use chatter::Chatter;
+use chatter_stats::{ChatterStats, ChatterStatsStore};
 use command::Command;
+use config::Config;
+use control_command::ControlCommand;
 use credits::Credits;
-use draw_system::{DrawSystem, PlayerDrawSystem, TimerDrawSystem};
+use debug_overlay::DebugOverlay;
+use draw_system::{DrawSystem, GameObjectDrawSystem, PlayerDrawSystem, TimerDrawSystem};
+use game_event::GameEvent;
 use game_object::GameObject;
 use game_object_type::GameObjectType;
 use ggez::audio;
 use ggez::audio::SoundSource;
-use ggez::event::EventHandler;
-use ggez::graphics::BLACK;
+use ggez::event::{EventHandler, KeyCode, KeyMods};
+use ggez::graphics::{Image, BLACK};
 use ggez::{graphics, timer, Context, GameResult};
+use high_scores::HighScoreStore;
+use hitbox::{PixelMask, PlacedHitbox};
+use input_state::InputState;
 use interface::Interface;
-use life_system::{LifeSystem, PlayerLifeSystem};
-use physics::{PhysicsSystem, PlayerPhysics, TimerPhysicsSystem};
+use life_system::LifeSystem;
+use object_catalog::ObjectCatalog;
+use personal_bests::{PersonalBestsStore, SurvivalRun};
+use physics::{PhysicsSystem, TimerPhysicsSystem};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use replay::{Replay, ReplayChatMessage};
 use running_state::RunningState;
+pub use simulation::Simulation;
+use sound_effect::SoundEffect;
 use splash::Splash;
 use sprites::Sprite;
-use std::{collections::HashMap, time::Duration};
-use std::{
-    sync::mpsc::{channel, Receiver, Sender},
-    time::Instant,
-};
-use twitch_chat_wrapper::chat_message::ChatMessage;
-
-pub const DROP_ZONE_COUNT: u8 = 10;
-const GAME_TIME: Duration = Duration::from_secs(120);
-pub const SPLASH_DURATION: Duration = Duration::from_secs(15);
-const LIVES: u8 = 3;
-const FRAMERATE_TARGET: u32 = 60;
-const SCORES_FILE_NAME: &str = "/high_scores";
+use std::collections::HashMap;
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use team::Team;
 
 pub struct GameState {
-    send_to_chat: Sender<String>,
-    receive_from_chat: Receiver<ChatMessage>,
+    receive_from_chat: Receiver<IncomingChat>,
+    chat_queue: ChatQueue,
+    chat_queries: ChatQueries,
+    high_score_store: HighScoreStore,
+    /// All-time scores, without the round being played
+    high_scores: HashMap<String, u128>,
+    personal_bests_store: PersonalBestsStore,
+    /// The longest endless mode runs, longest first
+    personal_bests: Vec<SurvivalRun>,
     screen_size: (f32, f32),
     interface: Interface,
-    game_objects: Vec<GameObject>,
-    player_hit_object_event: Receiver<Chatter>,
-    running_state: RunningState,
+    simulation: Simulation,
+    catalog: ObjectCatalog,
+    pending_chat_messages: Vec<ReplayChatMessage>,
+    recording: Option<Replay>,
+    /// Nothing but the credits has been recorded since the recording was last saved, so
+    /// quitting doesn't have to save it again
+    recording_saved: bool,
+    replay: Option<Replay>,
     credits: Option<Credits>,
     splash: Splash,
-    game_start_time: Instant,
+    paused: Splash,
+    debug_overlay: DebugOverlay,
+    /// What happened in the simulation, for the sounds and the debug overlay
+    game_events: Receiver<GameEvent>,
     object_sound: audio::Source,
-    scores: HashMap<String, u128>,
+    heart_sound: audio::Source,
+    jump_sound: audio::Source,
+    hit_sound: audio::Source,
 }
 
 impl GameState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         send_to_chat: Sender<String>,
-        receive_from_chat: Receiver<ChatMessage>,
+        receive_from_chat: Receiver<IncomingChat>,
         screen_size: (f32, f32),
+        seed: u64,
+        config: Config,
+        catalog: ObjectCatalog,
+        replay: Option<Replay>,
         context: &mut Context,
     ) -> GameResult<GameState> {
-        let game_started_message = format!("In {} seconds the Get the Streamer game will begin, you can play through chat with the commands on the right side of the game.", SPLASH_DURATION.as_secs());
-        send_to_chat.send(game_started_message).unwrap();
-        let mut interface = Interface::new(context, screen_size, LIVES)?;
-
-        // create timer block
-        let timer_game_object = Self::create_timer(
-            screen_size,
+        let interface = Interface::new(
             context,
-            interface.width,
-            SPLASH_DURATION,
-            (0.0, 1.0, 0.0),
+            screen_size,
+            vec![config.game.lives; config.coop.player_numbers().len()],
+            config.game.drop_zone_count,
         )?;
-        interface.add_game_object(timer_game_object);
-
-        // create player
-        let player_scale = 4.0;
-        let player_forward_sprite = Sprite::new(context, "/player_forward.png", 8, 1)?;
-        let player_left_sprite = Sprite::new(context, "/player_left.png", 8, 1)?;
-        let player_draw_system =
-            PlayerDrawSystem::new(player_left_sprite, player_forward_sprite, player_scale);
-        let player_size = player_draw_system.get_size().unwrap_or((50.0, 50.0));
-        let (send_player_hit_object_event, receive_player_hit_object_event) = channel();
-        let player_physics_system = PlayerPhysics::new(context, send_player_hit_object_event);
-        let player = GameObject::new(
-            250.0,
-            250.0,
-            Some(Box::new(player_draw_system)),
-            player_size.0,
-            player_size.1,
-            Some(Box::new(player_physics_system)),
-            true,
-            None,
-            GameObjectType::Player,
-            Some(Box::new(PlayerLifeSystem::new())),
-        );
 
-        let game_objects = vec![player];
-        let splash = Splash::new(
-            (screen_size.0 - interface.width, screen_size.1),
-            context,
-            SPLASH_DURATION,
-        );
-        let game_start_time = Instant::now();
+        let arena_size = (screen_size.0 - interface.width, screen_size.1);
+        let recording = if replay.is_none() {
+            Some(Replay::new(seed, config.clone(), catalog.clone()))
+        } else {
+            None
+        };
+        let chat_queue = ChatQueue::new(&config.chat);
+        let chat_queries = ChatQueries::new(send_to_chat.clone());
+        let high_score_store = HighScoreStore::in_user_config_dir(context);
+        let loaded_high_scores = high_score_store.load();
+        for problem in &loaded_high_scores.problems {
+            eprintln!("Problem loading high scores: {}", problem);
+        }
+        let personal_bests_store = PersonalBestsStore::in_user_config_dir(context);
+        let loaded_personal_bests = personal_bests_store.load();
+        for problem in &loaded_personal_bests.problems {
+            eprintln!("Problem loading personal bests: {}", problem);
+        }
+        let mut simulation =
+            Simulation::new(send_to_chat, arena_size, seed, config, catalog.clone());
+        for object in catalog.objects().iter().filter(|object| object.pixel_mask) {
+            let image = Image::new(context, &object.sprite.path)?;
+            let pixel_mask = PixelMask::from_rgba8(
+                image.width(),
+                image.height(),
+                &image.to_rgba8(context)?,
+                object.sprite.frames_across,
+                object.sprite.frames_down,
+            );
+            simulation.set_pixel_mask(&object.name, pixel_mask);
+        }
+        let splash = Splash::new(arena_size, context);
+        let paused = Splash::with_text("Paused", arena_size, context);
 
-        Ok(GameState {
-            send_to_chat,
+        let mut game_state = GameState {
             receive_from_chat,
+            chat_queue,
+            chat_queries,
+            high_score_store,
+            high_scores: loaded_high_scores.scores,
+            personal_bests_store,
+            personal_bests: loaded_personal_bests.runs,
             screen_size,
             interface,
-            game_objects,
-            player_hit_object_event: receive_player_hit_object_event,
-            running_state: RunningState::StartingSoon,
+            game_events: simulation.subscribe(),
+            simulation,
+            catalog,
+            pending_chat_messages: vec![],
+            recording,
+            recording_saved: false,
+            replay,
             credits: None,
             splash,
-            game_start_time,
-            object_sound: audio::Source::new(context, "/threeTone1.ogg").unwrap(),
-            scores: HashMap::new(),
-        })
+            paused,
+            debug_overlay: DebugOverlay::default(),
+            object_sound: audio::Source::new(context, "/threeTone1.ogg")?,
+            heart_sound: audio::Source::new(context, "/phaserUp7.ogg")?,
+            jump_sound: audio::Source::new(context, "/phaseJump1.ogg")?,
+            hit_sound: audio::Source::new(context, "/zap2.ogg")?,
+        };
+        game_state.attach_draw_systems(context)?;
+        game_state.enter_running_state(context)?;
+
+        Ok(game_state)
     }
 
-    fn handle_command(
-        &mut self,
-        command: Option<Command>,
-        context: &mut Context,
-    ) -> GameResult<()> {
-        if let Some(command) = command {
-            let chatter = command.chatter.clone();
-            self.object_sound.play().unwrap();
-            self.game_objects.push(command.handle(
-                self.interface.get_column_coordinates_by_index(command.id),
-                context,
-            )?);
-            let score = self.scores.entry(chatter.name).or_insert(0);
-            *score += 1;
+    /// The simulation creates game objects without any graphics, so they are added here
+    /// before the objects are drawn for the first time
+    fn attach_draw_systems(&mut self, context: &mut Context) -> GameResult<()> {
+        let catalog = &self.catalog;
+        for game_object in self.simulation.game_objects_mut() {
+            if game_object.has_draw_system() {
+                continue;
+            }
+
+            if game_object.my_type == GameObjectType::Player {
+                let player_forward_sprite = Sprite::new(context, "/player_forward.png", 8, 1)?;
+                let player_left_sprite = Sprite::new(context, "/player_left.png", 8, 1)?;
+                game_object.set_draw_system(Box::new(PlayerDrawSystem::new(
+                    player_left_sprite,
+                    player_forward_sprite,
+                    simulation::PLAYER_SCALE,
+                )));
+            } else if let Some(object) = game_object
+                .object_name
+                .as_ref()
+                .and_then(|object_name| catalog.get(object_name))
+            {
+                // premium drops can be bigger than the catalog says
+                let scale = game_object.location.w / object.sprite.frame_width;
+                let draw_system: GameObjectDrawSystem = Command::create_draw_system(
+                    object,
+                    game_object.chatter.as_ref(),
+                    scale,
+                    context,
+                )?;
+                game_object.set_draw_system(Box::new(draw_system));
+            }
         }
         Ok(())
     }
 
-    fn get_player(&self) -> Option<&GameObject> {
-        self.game_objects
-            .iter()
-            .find(|game_object| game_object.my_type == GameObjectType::Player)
+    /// Plays the sounds for what happened in the simulation and lists it on the debug overlay
+    fn handle_game_events(&mut self) {
+        for event in self.game_events.try_iter() {
+            self.debug_overlay.record_event(&event);
+            let source = match SoundEffect::for_event(&event) {
+                None => continue,
+                Some(SoundEffect::ObjectDropped) => &mut self.object_sound,
+                Some(SoundEffect::Jump) => &mut self.jump_sound,
+                Some(SoundEffect::Heart) => &mut self.heart_sound,
+                Some(SoundEffect::Hit) => &mut self.hit_sound,
+            };
+
+            if let Err(error) = source.play() {
+                eprintln!("error playing sound: {}", error);
+            }
+        }
+    }
+
+    /// Keeps the timers and credits in step with the simulation when it changes state
+    fn enter_running_state(&mut self, context: &mut Context) -> GameResult<()> {
+        let game_config = &self.simulation.config().game;
+        let (timer_duration, timer_color) = match self.simulation.running_state() {
+            RunningState::StartingSoon => (game_config.splash_duration(), (0.0, 1.0, 0.0)),
+            RunningState::Playing if self.simulation.wave().is_some() => (
+                self.simulation.config().endless.wave_time(),
+                (1.0, 0.5, 0.0),
+            ),
+            RunningState::Playing => (game_config.game_time(), (1.0, 0.0, 0.0)),
+            // the timer is left where it was until the game is resumed
+            RunningState::Paused => return Ok(()),
+            RunningState::ChatWon | RunningState::PlayerWon => {
+                self.end_round(context)?;
+                return Ok(());
+            }
+        };
+        self.credits = None;
+        self.interface.clear_game_objects();
+        // in endless mode the timer counts down each wave instead of the round
+        let timer_label = match (self.simulation.running_state(), self.simulation.wave()) {
+            (RunningState::Playing, Some(wave)) => Some(format!("Wave {}", wave)),
+            _ => None,
+        };
+        let timer = Self::create_timer(
+            self.screen_size,
+            context,
+            self.interface.width,
+            timer_duration,
+            timer_color,
+            timer_label.as_deref(),
+        )?;
+        self.interface.add_game_object(timer);
+        Ok(())
+    }
+
+    fn end_round(&mut self, context: &mut Context) -> GameResult<()> {
+        self.save_recording(context);
+        self.update_scores();
+        if let Err(error) = self.high_score_store.save(&self.high_scores) {
+            eprintln!("Error saving high scores to disk: {}", error);
+        }
+        let chatter_stats = self.update_chatter_stats(context);
+        let survival_run = self.record_survival_run();
+        let running_state = self.simulation.running_state();
+        let scores = self.simulation.scores().clone();
+        let team_scores = self.simulation.team_scores();
+        let session_leaderboard = if self.simulation.config().game.session_mode {
+            self.simulation.get_session_leaderboard()
+        } else {
+            vec![]
+        };
+        self.credits = Some(Credits::new(
+            running_state,
+            context,
+            self.screen_size,
+            &self.high_scores,
+            &scores,
+            &session_leaderboard,
+            &chatter_stats,
+            &team_scores,
+            survival_run.as_ref(),
+            &self.personal_bests,
+            // the credits get their own rng so picking a message doesn't change the next round
+            &mut StdRng::seed_from_u64(self.simulation.seed() ^ u64::from(self.simulation.round())),
+        )?);
+        Ok(())
+    }
+
+    /// Puts the endless mode run into the personal bests and saves them, `None` outside of
+    /// endless mode
+    fn record_survival_run(&mut self) -> Option<SurvivalRun> {
+        let wave = self.simulation.wave()?;
+        let ended_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        let run = SurvivalRun {
+            seconds: self.simulation.survival_time().as_secs(),
+            wave,
+            ended_at,
+            top_scorer: self
+                .simulation
+                .get_highest_scorer()
+                .map(|(name, _score)| name),
+        };
+        if personal_bests::add_run(&mut self.personal_bests, run.clone()).is_some() {
+            if let Err(error) = self.personal_bests_store.save(&self.personal_bests) {
+                eprintln!("Error saving personal bests to disk: {}", error);
+            }
+        }
+        Some(run)
     }
 
     fn create_timer(
@@ -155,11 +343,14 @@ impl GameState {
         interface_width: f32,
         duration: Duration,
         color: (f32, f32, f32),
+        label: Option<&str>,
     ) -> GameResult<GameObject> {
-        let timer_draw_system = TimerDrawSystem::new(screen_size, context, color)?;
+        let mut timer_draw_system = TimerDrawSystem::new(screen_size, context, color)?;
+        if let Some(label) = label {
+            timer_draw_system = timer_draw_system.with_label(label);
+        }
         let timer_size = timer_draw_system.get_size().unwrap_or((5.0, screen_size.1));
-        let timer_physics_system =
-            TimerPhysicsSystem::new(timer_size.1, duration, FRAMERATE_TARGET as f32);
+        let timer_physics_system = TimerPhysicsSystem::new(timer_size.1, duration);
         let timer_game_object = GameObject::new(
             screen_size.0 - interface_width,
             0.0,
@@ -175,180 +366,192 @@ impl GameState {
         Ok(timer_game_object)
     }
 
-    fn update_scores(&self, high_scores: &mut HashMap<String, u128>) {
-        for (username, score) in &self.scores {
-            let high_score = high_scores.entry(username.to_owned()).or_insert(0);
+    fn update_scores(&mut self) {
+        for (username, score) in self.simulation.scores() {
+            let high_score = self.high_scores.entry(username.to_owned()).or_insert(0);
             *high_score += *score;
         }
     }
 
-    fn send_game_started_message(&self) {
-        let message = format!(
-            "You have {} seconds to send your commands to Get the Streamer!",
-            GAME_TIME.as_secs()
-        );
-        if let Err(error) = self.send_to_chat.send(message) {
-            eprintln!("error sending game started message to chat: {}", error);
+    /// Adds this round to everyone's all-time stats and saves them
+    fn update_chatter_stats(&self, context: &Context) -> HashMap<String, ChatterStats> {
+        let chatter_stats_store = ChatterStatsStore::in_user_config_dir(context);
+        let loaded_chatter_stats = chatter_stats_store.load();
+        for problem in &loaded_chatter_stats.problems {
+            eprintln!("Problem loading chatter stats: {}", problem);
+        }
+        let mut chatter_stats = loaded_chatter_stats.chatters;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        for (username, round_stats) in self.simulation.round_stats() {
+            chatter_stats
+                .entry(username.to_owned())
+                .or_default()
+                .add_round(round_stats, now);
+        }
+        if let Err(error) = chatter_stats_store.save(&chatter_stats) {
+            eprintln!("Error saving chatter stats to disk: {}", error);
         }
+        chatter_stats
     }
 
-    fn send_game_ended_message(&self, winner: RunningState) {
-        let (highest_scorer, score) = self
-            .get_highest_scorer()
-            .unwrap_or_else(|| ("nobody".to_owned(), 0));
+    /// Handles this frame's share of the chat queue, queries are answered right away
+    /// and the rest waits for the next tick
+    fn receive_chat_messages(&mut self) {
+        let now = Instant::now();
+        self.chat_queue.receive(&self.receive_from_chat, now);
+        for chat_message in self.chat_queue.take(now) {
+            if self
+                .chat_queries
+                .handle(&chat_message, &self.simulation, &self.high_scores)
+            {
+                continue;
+            }
+            // control commands and team joins work in every state, drops only while playing.
+            // Cheers and redemptions are held by the simulation until then
+            let chat_message = ReplayChatMessage::new(self.simulation.ticks(), chat_message);
+            if ControlCommand::parse(&chat_message.message).is_some()
+                || Team::parse_join(&chat_message.message).is_some()
+                || chat_message.payment().is_some()
+                || matches!(self.simulation.running_state(), RunningState::Playing)
+            {
+                self.pending_chat_messages.push(chat_message);
+            }
+        }
+    }
 
-        let message = match winner {
-            RunningState::ChatWon => format!(
-                "You all won, highest scorer was {} with {} points!",
-                highest_scorer, score
+    fn get_debug_lines(&self, context: &Context) -> Vec<String> {
+        vec![
+            format!("fps: {:.0}", timer::fps(context)),
+            format!("chat queue: {}", self.chat_queue.len()),
+            format!(
+                "chat thrown away: {} too old, {} queue full",
+                self.chat_queue.dropped_stale(),
+                self.chat_queue.dropped_full()
             ),
-            _ => format!(
-                "The Streamer won the game despite the best efforts of {} who got {} points!",
-                highest_scorer, score
-            ),
-        };
+            format!("objects: {}", self.simulation.game_objects().len()),
+            "recent events:".to_owned(),
+        ]
+        .into_iter()
+        .chain(self.debug_overlay.recent_events().cloned())
+        .collect()
+    }
 
-        if let Err(error) = self.send_to_chat.send(message) {
-            eprintln!("error sending game ended message to chat: {}", error);
+    /// Get the input and chat messages for the next tick, either live or from the replay
+    fn get_next_tick(&mut self, context: &Context) -> (InputState, Vec<ReplayChatMessage>) {
+        let tick = self.simulation.ticks();
+        if let Some(replay) = &mut self.replay {
+            return (replay.get_input(tick), replay.take_chat_messages(tick));
         }
+
+        let chat_messages = self
+            .pending_chat_messages
+            .drain(..)
+            .map(|chat_message| ReplayChatMessage {
+                tick,
+                ..chat_message
+            })
+            .collect();
+        (InputState::from_keyboard(context), chat_messages)
     }
 
-    fn end_game(&mut self, new_running_state: RunningState) {
-        self.send_game_ended_message(new_running_state);
-        self.running_state = new_running_state;
+    /// For the rows of hearts in the sidebar
+    fn get_player_lives_left(&self) -> Vec<u8> {
+        self.simulation
+            .config()
+            .coop
+            .player_numbers()
+            .iter()
+            .map(|player_number| self.simulation.player_lives_left(*player_number))
+            .collect()
     }
 
-    fn get_highest_scorer(&self) -> Option<(String, u128)> {
-        if let Some(scorer) = self.scores.iter().max_by(|a, b| a.1.cmp(b.1)) {
-            Some((scorer.0.to_owned(), *scorer.1))
-        } else {
-            None
+    fn draw_boss_health(&self, context: &mut Context) -> GameResult<()> {
+        match self.simulation.boss_health() {
+            Some(health) => self.interface.draw_boss_health(
+                context,
+                self.screen_size,
+                health,
+                self.simulation.config().boss.health,
+            ),
+            None => Ok(()),
         }
     }
+
+    fn save_recording(&mut self, context: &mut Context) {
+        if let Some(recording) = &self.recording {
+            match recording.save(context) {
+                Ok(file_name) => println!("Replay of this game saved to {}", file_name),
+                Err(error) => eprintln!("Error saving replay to disk: {}", error),
+            }
+        }
+        self.recording_saved = true;
+    }
+
+    /// Quitting mid-round still writes the replay, rounds that already ended were saved then
+    fn quit(&mut self, context: &mut Context) {
+        if !self.recording_saved {
+            self.save_recording(context);
+        }
+        ggez::event::quit(context);
+    }
 }
+
 impl EventHandler for GameState {
     fn update(&mut self, context: &mut Context) -> GameResult {
-        if let Ok(chat_message) = self.receive_from_chat.try_recv() {
-            if matches!(self.running_state, RunningState::Playing) {
-                let chatter_name = if let Some(display_name) = chat_message.display_name {
-                    display_name
-                } else {
-                    chat_message.name.clone()
-                };
-                match Command::new(
-                    &chat_message.message,
-                    Chatter::new(
-                        chatter_name,
-                        chat_message.color_rgb,
-                        chat_message.subscriber,
-                    ),
-                ) {
-                    Err(error) => self.send_to_chat.send(error.to_owned()).unwrap(),
-                    Ok(command) => self.handle_command(command, context)?,
-                }
-            }
-        }
+        self.receive_chat_messages();
 
-        while timer::check_update_time(context, FRAMERATE_TARGET) {
-            match self.running_state {
-                RunningState::StartingSoon => {
-                    if let Err(error) = self.interface.update(context, LIVES) {
-                        eprintln!("Error updating game objects in interface: {}", error);
-                    }
-                    if self.splash.is_done() {
-                        self.send_game_started_message();
-                        self.running_state = RunningState::Playing;
-                        let timer = Self::create_timer(
-                            self.screen_size,
-                            context,
-                            self.interface.width,
-                            GAME_TIME,
-                            (1.0, 0.0, 0.0),
-                        )?;
-                        self.interface.add_game_object(timer);
-                        self.game_start_time = Instant::now();
-                    }
+        // ggez keeps the leftover time between frames, so however fast the machine draws the
+        // simulation always runs `framerate_target` fixed ticks a second
+        let framerate_target = self.simulation.config().game.framerate_target;
+        while timer::check_update_time(context, framerate_target) {
+            let previous_running_state = self.simulation.running_state();
+            let previous_wave = self.simulation.wave();
+            let (input, chat_messages) = self.get_next_tick(context);
+            if let Some(recording) = &mut self.recording {
+                recording.record(input, &chat_messages);
+                if !previous_running_state.is_game_over() {
+                    self.recording_saved = false;
                 }
-                RunningState::Playing => {
-                    // get the player lives left
-                    let lives_left = if let Some(player) = self.get_player() {
-                        player.get_lives_left().unwrap_or(3)
-                    } else {
-                        0
-                    };
-
-                    if let Err(error) = self.interface.update(context, lives_left) {
-                        eprintln!("Error updating game objects in interface: {}", error);
-                    }
-
-                    let game_time_left =
-                        GAME_TIME.as_secs() - self.game_start_time.elapsed().as_secs();
-                    if game_time_left == 0 {
-                        self.end_game(RunningState::PlayerWon);
-                    }
+            }
+            let commands = self.simulation.create_commands(&chat_messages);
+            self.simulation.tick(&input, commands);
+            self.attach_draw_systems(context)?;
+            self.handle_game_events();
 
-                    let arena_size = (
-                        self.screen_size.0 - self.interface.width,
-                        self.screen_size.1,
-                    );
-
-                    let collidable_game_objects: Vec<GameObject> = self
-                        .game_objects
-                        .clone()
-                        .into_iter()
-                        .filter(|game_object| game_object.collidable)
-                        .collect();
-
-                    self.game_objects.iter_mut().for_each(|game_object| {
-                        if let Err(error) = game_object.update(
-                            timer::time_since_start(context),
-                            arena_size,
-                            context,
-                            &collidable_game_objects,
-                        ) {
-                            eprintln!("error running update: {}", error)
-                        }
-                    });
-
-                    self.game_objects
-                        .retain(|game_object| game_object.is_alive());
-
-                    if let Ok(chatter) = self.player_hit_object_event.try_recv() {
-                        let message_to_chat = format!("Hit! {} gets 10 points", &chatter.name);
-                        self.send_to_chat.send(message_to_chat).unwrap();
-                        let score = self.scores.entry(chatter.name).or_insert(0);
-                        *score += 10;
-                    }
+            let running_state = self.simulation.running_state();
+            let pause_toggled = matches!(
+                (previous_running_state, running_state),
+                (RunningState::Playing, RunningState::Paused)
+                    | (RunningState::Paused, RunningState::Playing)
+            );
+            let wave_changed = running_state == RunningState::Playing
+                && previous_running_state == RunningState::Playing
+                && self.simulation.wave() != previous_wave;
+            if (running_state != previous_running_state && !pause_toggled) || wave_changed {
+                self.enter_running_state(context)?;
+            }
 
-                    if self
-                        .game_objects
-                        .iter()
-                        .find(|game_object| game_object.my_type == GameObjectType::Player)
-                        .is_none()
-                    {
-                        self.end_game(RunningState::ChatWon);
+            match self.simulation.running_state() {
+                RunningState::StartingSoon | RunningState::Playing => {
+                    if let Err(error) = self.interface.update(
+                        timer::time_since_start(context),
+                        self.simulation.config().game.tick_seconds(),
+                        self.get_player_lives_left(),
+                    ) {
+                        eprintln!("Error updating game objects in interface: {}", error);
                     }
                 }
+                RunningState::Paused => {}
                 RunningState::ChatWon | RunningState::PlayerWon => {
-                    if let Some(credits) = &mut self.credits {
-                        if !credits.update() {
-                            ggez::event::quit(context);
-                        }
-                    } else {
-                        let mut high_scores = utilities::load_scores(SCORES_FILE_NAME, context);
-                        self.update_scores(&mut high_scores);
-                        if let Err(error) =
-                            utilities::save_scores(context, SCORES_FILE_NAME, &high_scores)
-                        {
-                            eprintln!("Error saving high scores to disk: {}", error);
-                        }
-                        self.credits = Some(Credits::new(
-                            self.running_state,
-                            context,
-                            self.screen_size,
-                            &high_scores,
-                            &self.scores,
-                        )?);
+                    let credits_finished = self
+                        .credits
+                        .as_mut()
+                        .is_none_or(|credits| !credits.update());
+                    // in session mode the simulation starts the next round after the intermission
+                    if credits_finished && !self.simulation.config().game.session_mode {
+                        self.quit(context);
                     }
                 }
             }
@@ -359,15 +562,38 @@ impl EventHandler for GameState {
     fn draw(&mut self, context: &mut Context) -> GameResult {
         graphics::clear(context, BLACK);
 
+        let running_state = self.simulation.running_state();
         self.interface
-            .draw(context, self.screen_size, &self.running_state)?;
+            .draw(context, self.screen_size, &running_state)?;
+        if let Some(vote_tally) = self.simulation.vote_tally() {
+            if !running_state.is_game_over() {
+                let framerate_target = self.simulation.config().game.framerate_target as u64;
+                let seconds_left = vote_tally
+                    .ticks_left(self.simulation.ticks())
+                    .map(|ticks_left| ticks_left.div_ceil(framerate_target));
+                self.interface.draw_vote_tally(
+                    context,
+                    self.screen_size,
+                    vote_tally,
+                    seconds_left,
+                )?;
+            }
+        }
 
-        match self.running_state {
+        match running_state {
             RunningState::StartingSoon => self.splash.draw(context)?,
             RunningState::Playing => {
-                for game_object in self.game_objects.iter() {
+                for game_object in self.simulation.game_objects() {
                     game_object.draw(context)?;
                 }
+                self.draw_boss_health(context)?;
+            }
+            RunningState::Paused => {
+                for game_object in self.simulation.game_objects() {
+                    game_object.draw(context)?;
+                }
+                self.draw_boss_health(context)?;
+                self.paused.draw(context)?;
             }
             RunningState::PlayerWon | RunningState::ChatWon => {
                 if let Some(credits) = &self.credits {
@@ -376,6 +602,42 @@ impl EventHandler for GameState {
             }
         }
 
+        if self.debug_overlay.is_visible() {
+            // the objects are only drawn while the round is being played
+            if matches!(running_state, RunningState::Playing | RunningState::Paused) {
+                let hitboxes: Vec<PlacedHitbox> = self
+                    .simulation
+                    .game_objects()
+                    .iter()
+                    .filter(|game_object| game_object.collidable)
+                    .map(GameObject::placed_hitbox)
+                    .collect();
+                self.debug_overlay.draw_hitboxes(context, &hitboxes)?;
+            }
+            let debug_lines = self.get_debug_lines(context);
+            self.debug_overlay.draw(context, &debug_lines)?;
+        }
+
         graphics::present(context)
     }
+
+    fn key_down_event(
+        &mut self,
+        context: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        repeat: bool,
+    ) {
+        match keycode {
+            KeyCode::Escape => self.quit(context),
+            KeyCode::F3 if !repeat => self.debug_overlay.toggle(),
+            _ => {}
+        }
+    }
+
+    /// Closing the window
+    fn quit_event(&mut self, context: &mut Context) -> bool {
+        self.quit(context);
+        false
+    }
 }
@@ -0,0 +1,55 @@
+use ggez::graphics::Rect;
+
+use super::LifeSystem;
+use crate::game_event::DeathReason;
+
+/// Loses health when the streamer lands on it, with a moment after every hit where it can't
+/// be hurt so one jump is one hit
+#[derive(Debug)]
+pub struct BossLifeSystem {
+    health: u8,
+    iframes: u32,
+    iframes_left: u32,
+}
+
+impl BossLifeSystem {
+    pub fn new(health: u8, iframes: u32) -> BossLifeSystem {
+        BossLifeSystem {
+            health,
+            iframes,
+            iframes_left: 0,
+        }
+    }
+}
+
+impl LifeSystem for BossLifeSystem {
+    fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    fn hit(&mut self) -> bool {
+        if self.iframes_left == 0 && self.health > 0 {
+            self.health -= 1;
+            self.iframes_left = self.iframes;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {
+        self.iframes_left = self.iframes_left.saturating_sub(1);
+    }
+
+    fn gain_life(&mut self) -> bool {
+        false
+    }
+
+    fn get_lives_left(&self) -> u8 {
+        self.health
+    }
+
+    fn death_reason(&self) -> DeathReason {
+        DeathReason::Defeated
+    }
+}
@@ -1,38 +1,45 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
+use crate::game_event::DeathReason;
 
 #[derive(Debug)]
 pub struct FireLifeSystem {
-    birth_time: Instant,
-    time_to_live: Duration,
+    ticks_left: u32,
 }
 
 impl FireLifeSystem {
-    pub fn new() -> FireLifeSystem {
+    pub fn new(time_to_live: Duration, framerate_target: u32) -> FireLifeSystem {
         FireLifeSystem {
-            birth_time: Instant::now(),
-            time_to_live: Duration::from_secs(6),
+            ticks_left: (time_to_live.as_secs_f32() * framerate_target as f32) as u32,
         }
     }
 }
 
 impl LifeSystem for FireLifeSystem {
     fn is_alive(&self) -> bool {
-        self.birth_time.elapsed() < self.time_to_live
+        self.ticks_left > 0
     }
 
     fn hit(&mut self) -> bool {
         false
     }
 
-    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {}
+    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+    }
 
-    fn gain_life(&mut self) {}
+    fn gain_life(&mut self) -> bool {
+        false
+    }
 
     fn get_lives_left(&self) -> u8 {
         0
     }
+
+    fn death_reason(&self) -> DeathReason {
+        DeathReason::TimeRanOut
+    }
 }
@@ -1,6 +1,7 @@
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
+use crate::game_event::DeathReason;
 
 #[derive(Debug)]
 pub struct HeartLifeSystem {
@@ -37,9 +38,19 @@ impl LifeSystem for HeartLifeSystem {
         }
     }
 
-    fn gain_life(&mut self) {}
+    fn gain_life(&mut self) -> bool {
+        false
+    }
 
     fn get_lives_left(&self) -> u8 {
         0
     }
+
+    fn death_reason(&self) -> DeathReason {
+        if self.die_in == 0 {
+            DeathReason::PickedUp
+        } else {
+            DeathReason::LeftArena
+        }
+    }
 }
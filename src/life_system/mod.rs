@@ -1,9 +1,12 @@
+mod boss_life_system;
 mod fire_life_system;
 mod heart_life_system;
 mod player_life_system;
 mod snake_life_system;
 mod sword_life_system;
 
+use crate::game_event::DeathReason;
+pub use boss_life_system::BossLifeSystem;
 pub use fire_life_system::FireLifeSystem;
 use ggez::graphics::Rect;
 pub use heart_life_system::HeartLifeSystem;
@@ -18,6 +21,9 @@ where
     fn is_alive(&self) -> bool;
     fn hit(&mut self) -> bool;
     fn update(&mut self, screen_size: (f32, f32), location: &Rect);
-    fn gain_life(&mut self);
+    /// False when no life was gained, like when the streamer already has as many as they can
+    fn gain_life(&mut self) -> bool;
     fn get_lives_left(&self) -> u8;
+    /// Only asked once the object is no longer alive
+    fn death_reason(&self) -> DeathReason;
 }
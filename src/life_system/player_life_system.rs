@@ -1,36 +1,46 @@
 use ggez::graphics::Rect;
 
-use super::LifeSystem;
+use crate::config::PlayerConfig;
+use crate::game_event::DeathReason;
 
-const IFRAMES_REDUCE_EVERY: u32 = 720;
-const IFRAMES_REDUCE_BY: u8 = 10;
-const IFRAMES_MIN: u8 = 10;
-const IFRAMES_START: u8 = 120;
+use super::LifeSystem;
 
 #[derive(Debug)]
 pub struct PlayerLifeSystem {
     lives: u8,
+    /// 0 when there is no limit
+    max_lives: u8,
     iframes_left: u8,
     iframes: u8,
     frame_count: u32,
+    iframes_min: u8,
+    iframes_reduce_by: u8,
+    iframes_reduce_every: u32,
 }
 
 impl PlayerLifeSystem {
-    pub fn new() -> PlayerLifeSystem {
+    pub fn new(lives: u8, config: &PlayerConfig) -> PlayerLifeSystem {
         PlayerLifeSystem {
-            lives: 3,
+            lives,
+            max_lives: config.max_lives,
             iframes_left: 0,
-            iframes: IFRAMES_START,
-            frame_count: IFRAMES_REDUCE_EVERY,
+            iframes: config.iframes_start,
+            frame_count: config.iframes_reduce_every,
+            iframes_min: config.iframes_min,
+            iframes_reduce_by: config.iframes_reduce_by,
+            iframes_reduce_every: config.iframes_reduce_every,
         }
     }
 
     fn update_iframes(&mut self) {
-        if self.iframes > IFRAMES_MIN {
+        if self.iframes > self.iframes_min {
             self.frame_count -= 1;
             if self.frame_count == 0 {
-                self.frame_count = IFRAMES_REDUCE_EVERY;
-                self.iframes -= IFRAMES_REDUCE_BY;
+                self.frame_count = self.iframes_reduce_every;
+                self.iframes = self
+                    .iframes
+                    .saturating_sub(self.iframes_reduce_by)
+                    .max(self.iframes_min);
             }
         }
     }
@@ -62,11 +72,19 @@ impl LifeSystem for PlayerLifeSystem {
         self.update_iframes_left();
     }
 
-    fn gain_life(&mut self) {
+    fn gain_life(&mut self) -> bool {
+        if self.max_lives != 0 && self.lives >= self.max_lives {
+            return false;
+        }
         self.lives += 1;
+        true
     }
 
     fn get_lives_left(&self) -> u8 {
         self.lives
     }
+
+    fn death_reason(&self) -> DeathReason {
+        DeathReason::OutOfLives
+    }
 }
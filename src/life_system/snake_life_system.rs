@@ -1,6 +1,7 @@
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
+use crate::game_event::DeathReason;
 
 #[derive(Debug)]
 pub struct SnakeLifeSystem {
@@ -28,9 +29,15 @@ impl LifeSystem for SnakeLifeSystem {
         }
     }
 
-    fn gain_life(&mut self) {}
+    fn gain_life(&mut self) -> bool {
+        false
+    }
 
     fn get_lives_left(&self) -> u8 {
         0
     }
+
+    fn death_reason(&self) -> DeathReason {
+        DeathReason::LeftArena
+    }
 }
@@ -1,6 +1,7 @@
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
+use crate::game_event::DeathReason;
 
 #[derive(Debug)]
 pub struct SwordLifeSystem {
@@ -9,10 +10,10 @@ pub struct SwordLifeSystem {
 }
 
 impl SwordLifeSystem {
-    pub fn new() -> SwordLifeSystem {
+    pub fn new(breaks_at: u8) -> SwordLifeSystem {
         SwordLifeSystem {
             bounces: 0,
-            breaks_at: 7,
+            breaks_at,
         }
     }
 }
@@ -29,9 +30,15 @@ impl LifeSystem for SwordLifeSystem {
 
     fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {}
 
-    fn gain_life(&mut self) {}
+    fn gain_life(&mut self) -> bool {
+        false
+    }
 
     fn get_lives_left(&self) -> u8 {
         self.breaks_at - self.bounces
     }
+
+    fn death_reason(&self) -> DeathReason {
+        DeathReason::Broke
+    }
 }
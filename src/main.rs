@@ -1,19 +1,29 @@
+use eyre::Result;
+use get_the_streamer_game::chat_transport::{
+    ChatTransport, IncomingChat, IrcChatTransport, MockChatTransport, Scenario,
+    ScenarioChatTransport,
+};
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::replay::Replay;
 use get_the_streamer_game::GameState;
 use ggez::conf::{WindowMode, WindowSetup};
 use ggez::{event, ContextBuilder};
+use std::env;
 use std::sync::mpsc::channel;
 use std::thread;
-use twitch_chat_wrapper::chat_message::ChatMessage;
+use std::time::Duration;
 
 const WINDOW_SIZE: (f32, f32) = (1920.0, 1080.0);
+const LOCAL_IRC_CHANNEL: &str = "#local";
 
 fn main() {
-    let (send_to_game, receive_from_twitch) = channel::<ChatMessage>();
-    let (send_to_twitch, receive_from_game) = channel::<String>();
-
-    let _twitchchat_thread = thread::spawn(move || {
-        twitch_chat_wrapper::run(receive_from_game, send_to_game).unwrap();
+    let replay = get_argument("--replay").map(|path| match Replay::load(&path) {
+        Ok(replay) => replay,
+        Err(error) => panic!("could not load replay {}: {}", path, error),
     });
+    let (send_to_game, receive_from_chat) = channel::<IncomingChat>();
+    let (send_to_chat, receive_from_game) = channel::<String>();
 
     let (context, event_loop) = &mut match ContextBuilder::new("Get the Streamer", "Brooks Builds")
         .window_setup(WindowSetup::default().title("Get the Streamer"))
@@ -21,13 +31,133 @@ fn main() {
         .build()
     {
         Ok((context, event_loop)) => (context, event_loop),
-        Err(error) => panic!(error),
+        Err(error) => panic!("{}", error),
+    };
+
+    let config = if let Some(replay) = &replay {
+        replay.config.clone()
+    } else {
+        match Config::load(context) {
+            Ok(config) => config,
+            Err(error) => {
+                eprintln!("Error in the config file: {}", error);
+                return;
+            }
+        }
+    };
+
+    let catalog = if let Some(replay) = &replay {
+        replay.catalog.clone()
+    } else {
+        match ObjectCatalog::load(context) {
+            Ok(catalog) => catalog,
+            Err(error) => {
+                eprintln!("Error in the object catalog: {}", error);
+                return;
+            }
+        }
     };
 
-    let game_state =
-        &mut GameState::new(send_to_twitch, receive_from_twitch, WINDOW_SIZE, context).unwrap();
+    if let Err(error) = config.validate_objects(&catalog) {
+        eprintln!("Error in the config file: {}", error);
+        return;
+    }
+
+    let seed = if let Some(replay) = &replay {
+        replay.seed
+    } else {
+        get_seed_argument()
+            .or(config.game.seed)
+            .unwrap_or_else(rand::random)
+    };
+    println!(
+        "Random seed: {} (replay this game with --seed {})",
+        seed, seed
+    );
+
+    if replay.is_some() {
+        // replays don't connect to chat, what the game would have said in chat is printed instead
+        let _chat_thread = thread::spawn(move || {
+            for message in receive_from_game {
+                println!("chat: {}", message);
+            }
+        });
+    } else {
+        let chat_transport = match get_chat_transport(&config) {
+            Ok(chat_transport) => chat_transport,
+            Err(error) => {
+                eprintln!("Error connecting to chat: {}", error);
+                return;
+            }
+        };
+        let _chat_thread = thread::spawn(move || {
+            if let Err(error) = chat_transport.run(send_to_game, receive_from_game) {
+                eprintln!("Error in the chat connection: {}", error);
+            }
+        });
+    }
+
+    let game_state = &mut GameState::new(
+        send_to_chat,
+        receive_from_chat,
+        WINDOW_SIZE,
+        seed,
+        config,
+        catalog,
+        replay,
+        context,
+    )
+    .unwrap();
     match event::run(context, event_loop, game_state) {
         Ok(_) => println!("Thanks for playing!"),
         Err(error) => eprintln!("Error occurred: {}", error),
     };
 }
+
+/// Returns the value following `name` on the command line, like the path in `--replay <path>`
+fn get_argument(name: &str) -> Option<String> {
+    let mut arguments = env::args().skip_while(|argument| argument != name);
+    arguments.next()?;
+    arguments.next()
+}
+
+/// Twitch unless `--mock-chat`, `--scenario <file>` or `--irc <address>` ask for something else
+fn get_chat_transport(config: &Config) -> Result<Box<dyn ChatTransport>> {
+    if env::args().any(|argument| argument == "--mock-chat") {
+        return Ok(Box::new(MockChatTransport {
+            num_bot_users: 5,
+            initial_commands_delay: Duration::from_secs(0),
+            min_command_interval_ms: 250,
+            max_command_interval_ms: 1500,
+            cheer_chance: 0.02,
+        }));
+    }
+
+    if let Some(path) = get_argument("--scenario") {
+        return Ok(Box::new(ScenarioChatTransport {
+            scenario: Scenario::load(path)?,
+            splash_duration: config.game.splash_duration(),
+        }));
+    }
+
+    if let Some(address) = get_argument("--irc") {
+        return Ok(Box::new(IrcChatTransport::anonymous(
+            &address,
+            vec![LOCAL_IRC_CHANNEL.to_owned()],
+        )?));
+    }
+
+    Ok(Box::new(IrcChatTransport::twitch()?))
+}
+
+/// Reads the seed from `--seed <number>` on the command line
+fn get_seed_argument() -> Option<u64> {
+    let seed = get_argument("--seed")?;
+    match seed.parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => {
+            eprintln!("--seed needs a whole number, using a random seed instead");
+            None
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use std::collections::HashSet;
+use std::io::Read;
+
+use eyre::{bail, eyre, Result};
+use ggez::{filesystem, Context};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{check, GameConfig};
+use crate::game_object_type::GameObjectType;
+use crate::hitbox::HitboxShape;
+
+/// Looked up in the resources folder first and then in the user config directory,
+/// so a themed pack can replace it together with its sprites
+pub const OBJECT_CATALOG_FILE_NAME: &str = "/objects.toml";
+
+/// The objects the game ships with, also used by headless simulations
+const BUILT_IN_CATALOG: &str = include_str!("../assets/objects.toml");
+
+/// These always drop a random object, so they can't be used as aliases
+pub const RANDOM_ALIASES: [&str; 3] = ["random", "rand", "rng"];
+
+/// Version 1 catalogs had speeds in pixels per tick, they are converted when loaded
+const CATALOG_VERSION: u32 = 2;
+
+/// Every object that chat can drop into the arena
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectCatalog {
+    /// Catalogs without one are from version 1, before the version was written
+    version: u32,
+    objects: Vec<ObjectDefinition>,
+    /// What happens when dropped objects touch each other, nothing does unless it is listed here
+    #[serde(default)]
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectDefinition {
+    pub name: String,
+    /// Chat commands that drop this object, without the leading #
+    pub aliases: Vec<String>,
+    /// How likely #random is to pick this object compared to the others
+    pub weight: u32,
+    /// How long a chatter waits before dropping this object again, on top of the drop cooldown
+    #[serde(default)]
+    pub cooldown_seconds: f32,
+    pub effect: PlayerEffect,
+    pub scale: f32,
+    pub sprite: SpriteSheet,
+    pub physics: PhysicsPreset,
+    pub lifetime: LifetimeRule,
+    /// The part of the sprite that hurts or heals, the whole sprite when left out
+    #[serde(default)]
+    pub hitbox: HitboxShape,
+    /// Also skip the see-through pixels of the sprite, this only works when the sprites are loaded
+    /// so headless simulations use the hitbox alone
+    #[serde(default)]
+    pub pixel_mask: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpriteSheet {
+    pub path: String,
+    pub frames_across: u16,
+    pub frames_down: u16,
+    /// The size of a single frame is known up front
+    /// so that objects can be sized without loading the image
+    pub frame_width: f32,
+    pub frame_height: f32,
+}
+
+/// What happens to the streamer when they touch the object
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerEffect {
+    Damage,
+    Heal,
+}
+
+/// How the object moves once it has been dropped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "preset", rename_all = "snake_case", deny_unknown_fields)]
+pub enum PhysicsPreset {
+    /// Falls to the floor and stays there
+    FallAndStay,
+    /// Bounces off the floor, heading sideways at up to `max_bounce_speed` pixels per second
+    /// after the first bounce
+    Bounce {
+        max_bounce_speed: f32,
+        bounciness: f32,
+    },
+    /// Falls to the floor and then heads for the streamer at `speed` pixels per second,
+    /// leaving out the speed uses `physics.snake_speed` from the game config
+    Slither { speed: Option<f32> },
+    /// Falls slower than everything else
+    FloatDown { gravity_scale: f32 },
+}
+
+/// What happens to `object` when it touches `touches`, both are object names
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Interaction {
+    pub object: String,
+    pub touches: String,
+    pub outcome: InteractionOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionOutcome {
+    /// Goes away, like a sword melting in fire
+    Destroyed,
+    /// Heads away from the other object, only objects with the bounce preset move sideways
+    Bounce,
+}
+
+/// When the object goes away
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LifetimeRule {
+    Timed {
+        seconds: f32,
+    },
+    /// Only the bounce physics preset counts bounces, so it can't be used with the others
+    Bounces {
+        count: u8,
+    },
+    UntilOffScreen,
+    /// Only the float down physics preset is picked up, so it can't be used with the others
+    UntilPickedUp,
+}
+
+impl Default for ObjectCatalog {
+    fn default() -> Self {
+        ObjectCatalog::from_toml(BUILT_IN_CATALOG).expect("the built in object catalog is invalid")
+    }
+}
+
+impl ObjectCatalog {
+    /// Loads the catalog file if there is one, otherwise the built in objects are used
+    pub fn load(context: &mut Context) -> Result<ObjectCatalog> {
+        if !filesystem::exists(context, OBJECT_CATALOG_FILE_NAME) {
+            return Ok(ObjectCatalog::default());
+        }
+
+        let mut raw_catalog = String::new();
+        filesystem::open(context, OBJECT_CATALOG_FILE_NAME)?.read_to_string(&mut raw_catalog)?;
+        ObjectCatalog::from_toml(&raw_catalog)
+            .map_err(|error| eyre!("{}: {}", OBJECT_CATALOG_FILE_NAME, error))
+    }
+
+    pub fn from_toml(raw_catalog: &str) -> Result<ObjectCatalog> {
+        let mut raw_values: toml::Value = toml::from_str(raw_catalog)?;
+        let version = raw_values
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .unwrap_or(1);
+        if version > CATALOG_VERSION as i64 {
+            bail!(
+                "written by a newer version of the game (catalog version {})",
+                version
+            );
+        }
+        let catalog: ObjectCatalog = if version < 2 {
+            convert_from_per_tick_units(&mut raw_values);
+            toml::from_str(&toml::to_string(&raw_values)?)?
+        } else {
+            toml::from_str(raw_catalog)?
+        };
+        catalog.validate()?;
+        Ok(catalog)
+    }
+
+    pub fn objects(&self) -> &[ObjectDefinition] {
+        &self.objects
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ObjectDefinition> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    /// Whether anything happens to `object` when it touches other objects
+    pub fn has_interactions(&self, object: &str) -> bool {
+        self.interactions
+            .iter()
+            .any(|interaction| interaction.object == object)
+    }
+
+    /// What happens to `object` when it touches `touches`, if anything
+    pub fn interaction(&self, object: &str, touches: &str) -> Option<InteractionOutcome> {
+        self.interactions
+            .iter()
+            .find(|interaction| interaction.object == object && interaction.touches == touches)
+            .map(|interaction| interaction.outcome)
+    }
+
+    /// Find the object a chat command like `sword` drops
+    pub fn find_by_alias(&self, alias: &str) -> Option<&ObjectDefinition> {
+        self.objects.iter().find(|object| {
+            object
+                .aliases
+                .iter()
+                .any(|object_alias| object_alias == alias)
+        })
+    }
+
+    /// Pick an object for #random, objects with a higher weight come up more often
+    pub fn choose_random(&self, rng: &mut impl Rng) -> &ObjectDefinition {
+        self.objects
+            .choose_weighted(rng, |object| object.weight)
+            .expect("validated catalogs always have an object with weight")
+    }
+
+    /// Catches catalogs that parse fine but would break the game
+    pub fn validate(&self) -> Result<()> {
+        if self.objects.iter().all(|object| object.weight == 0) {
+            return Err(eyre!("at least one object needs a `weight` above 0"));
+        }
+
+        let mut names = HashSet::new();
+        let mut aliases = HashSet::new();
+        for object in &self.objects {
+            if !names.insert(object.name.as_str()) {
+                return Err(eyre!(
+                    "there is more than one object named `{}`",
+                    object.name
+                ));
+            }
+            object
+                .validate(&mut aliases)
+                .map_err(|error| eyre!("object `{}`: {}", object.name, error))?;
+        }
+
+        let mut pairs = HashSet::new();
+        for interaction in &self.interactions {
+            for name in &[&interaction.object, &interaction.touches] {
+                if !names.contains(name.as_str()) {
+                    return Err(eyre!(
+                        "interaction between `{}` and `{}`: there is no object named `{}`",
+                        interaction.object,
+                        interaction.touches,
+                        name
+                    ));
+                }
+            }
+            if !pairs.insert((&interaction.object, &interaction.touches)) {
+                return Err(eyre!(
+                    "there is more than one interaction for `{}` touching `{}`",
+                    interaction.object,
+                    interaction.touches
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ObjectDefinition {
+    pub fn get_size(&self) -> (f32, f32) {
+        (
+            self.sprite.frame_width * self.scale,
+            self.sprite.frame_height * self.scale,
+        )
+    }
+
+    pub fn get_game_object_type(&self) -> GameObjectType {
+        match self.effect {
+            PlayerEffect::Damage => GameObjectType::Enemy,
+            PlayerEffect::Heal => GameObjectType::Heart,
+        }
+    }
+
+    fn validate<'a>(&'a self, aliases: &mut HashSet<&'a str>) -> Result<()> {
+        check(
+            !self.aliases.is_empty(),
+            "aliases",
+            "have at least one alias",
+        )?;
+        for alias in &self.aliases {
+            check(
+                !alias.is_empty()
+                    && !alias.contains(char::is_whitespace)
+                    && !alias.starts_with('#'),
+                "aliases",
+                "be single words without the leading #",
+            )?;
+            check(
+                !RANDOM_ALIASES.contains(&alias.as_str()),
+                "aliases",
+                "not use random, rand or rng",
+            )?;
+            if !aliases.insert(alias) {
+                return Err(eyre!("the alias `{}` is used by another object", alias));
+            }
+        }
+        check(
+            self.cooldown_seconds.is_finite() && self.cooldown_seconds >= 0.0,
+            "cooldown_seconds",
+            "be a number that isn't negative",
+        )?;
+        check(
+            self.scale.is_finite() && self.scale > 0.0,
+            "scale",
+            "be a number above 0",
+        )?;
+        check(
+            self.sprite.frames_across > 0 && self.sprite.frames_down > 0,
+            "sprite",
+            "have at least one frame across and down",
+        )?;
+        check(
+            self.sprite.frame_width.is_finite()
+                && self.sprite.frame_height.is_finite()
+                && self.sprite.frame_width > 0.0
+                && self.sprite.frame_height > 0.0,
+            "sprite",
+            "have a frame_width and frame_height that are numbers above 0",
+        )?;
+
+        match &self.physics {
+            PhysicsPreset::Bounce {
+                max_bounce_speed,
+                bounciness,
+            } => {
+                check(
+                    max_bounce_speed.is_finite() && *max_bounce_speed >= 0.0,
+                    "physics.max_bounce_speed",
+                    "be a number that isn't negative",
+                )?;
+                check(
+                    bounciness.is_finite() && *bounciness >= 0.0,
+                    "physics.bounciness",
+                    "be a number that isn't negative",
+                )?;
+            }
+            PhysicsPreset::Slither { speed: Some(speed) } => {
+                check(
+                    speed.is_finite() && *speed >= 0.0,
+                    "physics.speed",
+                    "be a number that isn't negative",
+                )?;
+            }
+            PhysicsPreset::FloatDown { gravity_scale } => {
+                check(
+                    gravity_scale.is_finite() && *gravity_scale > 0.0,
+                    "physics.gravity_scale",
+                    "be a number above 0",
+                )?;
+            }
+            PhysicsPreset::FallAndStay | PhysicsPreset::Slither { speed: None } => {}
+        }
+
+        match self.hitbox {
+            HitboxShape::Inset {
+                left,
+                right,
+                top,
+                bottom,
+            } => {
+                check(
+                    left >= 0.0 && right >= 0.0 && top >= 0.0 && bottom >= 0.0,
+                    "hitbox",
+                    "not have negative insets",
+                )?;
+                check(
+                    left + right < 1.0 && top + bottom < 1.0,
+                    "hitbox",
+                    "leave some of the sprite between the insets",
+                )?;
+            }
+            HitboxShape::Circle { radius } => {
+                check(
+                    radius.is_finite() && radius > 0.0,
+                    "hitbox.radius",
+                    "be a number above 0",
+                )?;
+            }
+            HitboxShape::Oriented {
+                width,
+                height,
+                angle,
+            } => {
+                check(
+                    width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0,
+                    "hitbox",
+                    "have a width and height that are numbers above 0",
+                )?;
+                check(angle.is_finite(), "hitbox.angle", "be a number")?;
+            }
+            HitboxShape::Bounds => {}
+        }
+
+        match self.lifetime {
+            LifetimeRule::Timed { seconds } => {
+                check(
+                    seconds.is_finite() && seconds > 0.0,
+                    "lifetime.seconds",
+                    "be a number above 0",
+                )?;
+            }
+            LifetimeRule::Bounces { count } => {
+                check(count > 0, "lifetime.count", "be above 0")?;
+            }
+            LifetimeRule::UntilOffScreen | LifetimeRule::UntilPickedUp => {}
+        }
+
+        // bounces are counted and pickups happen in the presets, with any other preset the
+        // object would never go away and a heal would be given on every tick it's touched
+        check(
+            !matches!(self.lifetime, LifetimeRule::Bounces { .. })
+                || matches!(self.physics, PhysicsPreset::Bounce { .. }),
+            "lifetime",
+            "only be `bounces` with the `bounce` physics preset",
+        )?;
+        check(
+            self.lifetime != LifetimeRule::UntilPickedUp
+                || matches!(self.physics, PhysicsPreset::FloatDown { .. }),
+            "lifetime",
+            "only be `until_picked_up` with the `float_down` physics preset",
+        )?;
+        check(
+            self.effect != PlayerEffect::Heal || self.lifetime == LifetimeRule::UntilPickedUp,
+            "effect",
+            "only be `heal` with the `until_picked_up` lifetime",
+        )?;
+        Ok(())
+    }
+}
+
+/// Version 1 speeds were in pixels per tick, and catalogs didn't know the tick rate so they
+/// were made for the default one
+fn convert_from_per_tick_units(raw_values: &mut toml::Value) {
+    let ticks_per_second = GameConfig::default().framerate_target as f64;
+    let objects = raw_values
+        .get_mut("objects")
+        .and_then(toml::Value::as_array_mut);
+    for object in objects.into_iter().flatten() {
+        let physics = object
+            .get_mut("physics")
+            .and_then(toml::Value::as_table_mut);
+        for (key, value) in physics.into_iter().flatten() {
+            if key != "max_bounce_speed" && key != "speed" {
+                continue;
+            }
+            // other types are left for the error when the catalog is read
+            let speed = match value {
+                toml::Value::Float(speed) => *speed,
+                toml::Value::Integer(speed) => *speed as f64,
+                _ => continue,
+            };
+            *value = toml::Value::Float(speed * ticks_per_second);
+        }
+    }
+    if let Some(raw_catalog) = raw_values.as_table_mut() {
+        raw_catalog.insert(
+            "version".to_owned(),
+            toml::Value::Integer(CATALOG_VERSION as i64),
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+use eyre::{bail, Result};
+use ggez::{filesystem, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::utilities;
+
+/// Lives in the ggez user config directory, next to the high scores
+pub const PERSONAL_BESTS_FILE_NAME: &str = "personal_bests.json";
+const FORMAT_VERSION: u32 = 1;
+/// How many of the longest runs are kept
+pub const PERSONAL_BESTS_SIZE: usize = 10;
+
+/// One endless mode round, from the start until the streamer ran out of lives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalRun {
+    pub seconds: u64,
+    pub wave: u32,
+    /// Unix seconds
+    pub ended_at: u64,
+    /// Whoever in chat scored the most that run
+    pub top_scorer: Option<String>,
+}
+
+impl SurvivalRun {
+    /// Like `3:25`
+    pub fn format_time(&self) -> String {
+        format!("{}:{:02}", self.seconds / 60, self.seconds % 60)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PersonalBestsFile {
+    version: u32,
+    /// Longest first
+    runs: Vec<SurvivalRun>,
+}
+
+/// The streamer's longest endless mode runs, saved the same way as the high scores
+#[derive(Debug, Clone)]
+pub struct PersonalBestsStore {
+    directory: PathBuf,
+}
+
+/// The runs that could be read, along with everything that was wrong with the files
+#[derive(Debug, Default)]
+pub struct LoadedPersonalBests {
+    pub runs: Vec<SurvivalRun>,
+    pub problems: Vec<String>,
+}
+
+impl PersonalBestsStore {
+    pub fn new(directory: impl Into<PathBuf>) -> PersonalBestsStore {
+        PersonalBestsStore {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn in_user_config_dir(context: &Context) -> PersonalBestsStore {
+        PersonalBestsStore::new(filesystem::user_config_dir(context))
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(PERSONAL_BESTS_FILE_NAME)
+    }
+
+    /// Never fails, anything that can't be read is reported in `problems` and left out
+    pub fn load(&self) -> LoadedPersonalBests {
+        let mut problems = vec![];
+        let runs = utilities::read_with_backup(&self.path(), parse_runs_file, &mut problems)
+            .unwrap_or_default();
+        LoadedPersonalBests { runs, problems }
+    }
+
+    pub fn save(&self, runs: &[SurvivalRun]) -> Result<()> {
+        let runs_file = PersonalBestsFile {
+            version: FORMAT_VERSION,
+            runs: runs.to_vec(),
+        };
+        utilities::write_atomically(&self.path(), &serde_json::to_string_pretty(&runs_file)?)
+    }
+}
+
+/// Puts the run in its place in the table, returning where it ended up, or `None` when it
+/// wasn't long enough to make the table
+pub fn add_run(runs: &mut Vec<SurvivalRun>, run: SurvivalRun) -> Option<usize> {
+    // ties go to the older run
+    let place = runs
+        .iter()
+        .position(|best| run.seconds > best.seconds)
+        .unwrap_or(runs.len());
+    if place >= PERSONAL_BESTS_SIZE {
+        return None;
+    }
+    runs.insert(place, run);
+    runs.truncate(PERSONAL_BESTS_SIZE);
+    Some(place)
+}
+
+fn parse_runs_file(raw_runs: &str) -> Result<Vec<SurvivalRun>> {
+    let runs_file: PersonalBestsFile = serde_json::from_str(raw_runs)?;
+    if runs_file.version > FORMAT_VERSION {
+        bail!(
+            "written by a newer version of the game (format version {})",
+            runs_file.version
+        );
+    }
+    let mut runs = runs_file.runs;
+    runs.sort_by_key(|run| Reverse(run.seconds));
+    Ok(runs)
+}
@@ -0,0 +1,99 @@
+use super::PhysicsSystem;
+use crate::{
+    boss::BossAction, broadphase::Broadphase, hitbox::Hitbox, input_state::InputState,
+    life_system::LifeSystem,
+};
+use eyre::Result;
+use ggez::graphics::Rect;
+use ggez::nalgebra::Point2;
+use std::sync::mpsc::Receiver;
+
+/// How quickly the boss loses its sideways speed, per second
+const FRICTION: f32 = 1.2;
+/// How much faster the boss falls when it slams
+const SLAM_GRAVITY_SCALE: f32 = 3.0;
+
+/// Walks and jumps wherever chat tells it to, the actions come in from the simulation
+#[derive(Debug)]
+pub struct BossPhysics {
+    velocity: Point2<f32>,
+    actions: Receiver<BossAction>,
+    speed: f32,
+    jump_force: f32,
+    slamming: bool,
+}
+
+impl BossPhysics {
+    pub fn new(actions: Receiver<BossAction>, speed: f32, jump_force: f32) -> BossPhysics {
+        BossPhysics {
+            velocity: Point2::new(0.0, 0.0),
+            actions,
+            speed,
+            jump_force,
+            slamming: false,
+        }
+    }
+
+    fn handle_action(&mut self, action: BossAction, on_ground: bool) {
+        match action {
+            BossAction::Left => self.velocity.x = -self.speed,
+            BossAction::Right => self.velocity.x = self.speed,
+            BossAction::Slam if on_ground => {
+                self.velocity.y = self.jump_force;
+                self.slamming = true;
+            }
+            // spitting is done by the simulation, it's the one that can add game objects
+            BossAction::Slam | BossAction::Spit => {}
+        }
+    }
+}
+
+impl PhysicsSystem for BossPhysics {
+    fn update(
+        &mut self,
+        dt: f32,
+        location: &mut Rect,
+        screen_size: (f32, f32),
+        gravity_force: f32,
+        _input: &InputState,
+        _broadphase: &Broadphase,
+        _hitbox: &Hitbox,
+        _rotation: &mut f32,
+        _life_system: &mut Option<Box<dyn LifeSystem>>,
+    ) -> Result<()> {
+        let on_ground = location.y + location.h >= screen_size.1;
+        let actions: Vec<BossAction> = self.actions.try_iter().collect();
+        for action in actions {
+            self.handle_action(action, on_ground);
+        }
+
+        let falling = self.velocity.y > 0.0;
+        let gravity_force = if self.slamming && falling {
+            gravity_force * SLAM_GRAVITY_SCALE
+        } else {
+            gravity_force
+        };
+        self.velocity.y += gravity_force * dt;
+        self.velocity.x *= (-FRICTION * dt).exp();
+        location.x += self.velocity.x * dt;
+        location.y += self.velocity.y * dt;
+
+        if location.y + location.h > screen_size.1 {
+            location.y = screen_size.1 - location.h;
+            self.velocity.y = 0.0;
+            self.slamming = false;
+        }
+        if location.x < 0.0 {
+            location.x = 0.0;
+            self.velocity.x *= -1.0;
+        } else if location.x + location.w > screen_size.0 {
+            location.x = screen_size.0 - location.w;
+            self.velocity.x *= -1.0;
+        }
+        Ok(())
+    }
+
+    fn get_velocity_x(&self) -> f32 {
+        self.velocity.x
+    }
+}
@@ -1,10 +1,11 @@
-use crate::life_system::LifeSystem;
+use crate::{
+    broadphase::Broadphase, hitbox::Hitbox, input_state::InputState, life_system::LifeSystem,
+};
 
-use super::{GameObject, PhysicsSystem};
+use super::PhysicsSystem;
 use eyre::Result;
 use ggez::graphics::Rect;
 use ggez::nalgebra::Point2;
-use ggez::Context;
 
 #[derive(Debug)]
 pub struct FirePhysics {
@@ -24,20 +25,22 @@ impl FirePhysics {
 impl PhysicsSystem for FirePhysics {
     fn update(
         &mut self,
+        dt: f32,
         location: &mut Rect,
         screen_height: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
-        _collidable_game_objects: &[GameObject],
+        _input: &InputState,
+        _broadphase: &Broadphase,
+        _hitbox: &Hitbox,
         _rotation: &mut f32,
         _life_system: &mut Option<Box<dyn LifeSystem>>,
     ) -> Result<()> {
         if self.affected_by_gravity {
-            self.velocity.y += gravity_force;
+            self.velocity.y += gravity_force * dt;
         }
 
-        location.x += self.velocity.x;
-        location.y += self.velocity.y;
+        location.x += self.velocity.x * dt;
+        location.y += self.velocity.y * dt;
 
         if location.y + location.h >= screen_height.1 {
             self.velocity.y = 0.0;
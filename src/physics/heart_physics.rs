@@ -1,42 +1,50 @@
-use crate::{game_object::GameObject, game_object_type::GameObjectType, life_system::LifeSystem};
+use crate::{
+    broadphase::Broadphase, game_object_type::GameObjectType, hitbox::Hitbox,
+    input_state::InputState, life_system::LifeSystem,
+};
 
 use super::PhysicsSystem;
 use eyre::Result;
-use ggez::{graphics::Rect, Context};
+use ggez::graphics::Rect;
 
 #[derive(Debug)]
 pub struct HeartPhysics {
     velocity_y: f32,
+    gravity_scale: f32,
 }
 
 impl HeartPhysics {
-    pub fn new() -> Self {
-        Self { velocity_y: 0.0 }
+    pub fn new(gravity_scale: f32) -> Self {
+        Self {
+            velocity_y: 0.0,
+            gravity_scale,
+        }
     }
 }
 
 impl PhysicsSystem for HeartPhysics {
     fn update(
         &mut self,
+        dt: f32,
         location: &mut Rect,
         _screen_size: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
-        collidable_game_objects: &[GameObject],
-        _rotation: &mut f32,
+        _input: &InputState,
+        broadphase: &Broadphase,
+        hitbox: &Hitbox,
+        rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
     ) -> Result<()> {
-        self.velocity_y += gravity_force / 4.0;
-        location.y += self.velocity_y;
+        self.velocity_y += gravity_force * self.gravity_scale * dt;
+        location.y += self.velocity_y * dt;
 
-        if let Some(player) = collidable_game_objects
+        let picked_up = broadphase
+            .objects_touching(&hitbox.place(*location, *rotation))
             .iter()
-            .find(|game_object| game_object.my_type == GameObjectType::Player)
-        {
-            if location.overlaps(&player.location) {
-                if let Some(life_system) = life_system {
-                    life_system.hit();
-                }
+            .any(|collider| collider.my_type == GameObjectType::Player);
+        if picked_up {
+            if let Some(life_system) = life_system {
+                life_system.hit();
             }
         }
 
@@ -1,3 +1,4 @@
+mod boss_physics;
 pub mod fire_physics;
 mod heart_physics;
 mod player_physics;
@@ -5,34 +6,46 @@ mod snake_physics;
 mod sword_physics;
 mod timer_physics;
 
-use crate::life_system::LifeSystem;
+use crate::{
+    broadphase::Broadphase, hitbox::Hitbox, input_state::InputState, life_system::LifeSystem,
+};
 
-use super::{Chatter, GameObject};
+use super::Chatter;
+pub use boss_physics::BossPhysics;
 use eyre::Result;
 pub use fire_physics::FirePhysics;
 use ggez::graphics::Rect;
-use ggez::Context;
 pub use heart_physics::HeartPhysics;
 pub use player_physics::PlayerPhysics;
 pub use snake_physics::SnakePhysics;
 pub use sword_physics::SwordPhysics;
 pub use timer_physics::TimerPhysicsSystem;
 
+/// Velocities are in pixels per second and accelerations like `gravity_force` in pixels per
+/// second squared, so that the game plays the same whatever the tick rate is
 pub trait PhysicsSystem
 where
     Self: std::fmt::Debug,
 {
-    #[allow(clippy::clippy::too_many_arguments)]
+    /// `dt` is the length of the tick in seconds, `hitbox` is the object's own for checking what
+    /// it touches
+    #[allow(clippy::too_many_arguments)]
     fn update(
         &mut self,
+        dt: f32,
         location: &mut Rect,
         screen_size: (f32, f32),
         gravity_force: f32,
-        context: &mut Context,
-        collidable_game_objects: &[GameObject],
+        input: &InputState,
+        broadphase: &Broadphase,
+        hitbox: &Hitbox,
         rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
     ) -> Result<()>;
 
     fn get_velocity_x(&self) -> f32;
+
+    /// Called when the object touches another one it bounces off in the catalog's interactions,
+    /// most objects keep going
+    fn bounce_off(&mut self, _location: &Rect, _other: &Rect) {}
 }
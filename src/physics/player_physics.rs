@@ -1,56 +1,65 @@
-use crate::{game_object_type::GameObjectType, life_system::LifeSystem};
-
-use super::{Chatter, GameObject, PhysicsSystem};
+use crate::{
+    config::PlayerConfig,
+    game_event::GameEvent,
+    game_object_type::GameObjectType,
+    input_state::{InputState, PlayerNumber},
+    life_system::LifeSystem,
+};
+
+use super::{Chatter, PhysicsSystem};
+use crate::broadphase::{Broadphase, Collider};
+use crate::hitbox::{Hitbox, PlacedHitbox};
 use eyre::Result;
-use ggez::audio;
-use ggez::audio::SoundSource;
 use ggez::graphics::Rect;
-use ggez::input::keyboard::KeyCode;
 use ggez::nalgebra::Point2;
-use ggez::{input, Context};
 use std::sync::mpsc::Sender;
 
-const MOVE_FORCE: f32 = 2.0;
-const JUMP_FORCE: f32 = -12.5;
-const FRICTION: f32 = 0.15;
 const DEFAULT_CHATTER_NAME: &str = "Unknown Player";
+/// Landing in the top 1 / this of the boss counts as a stomp, deeper is running into it
+const STOMP_DEPTH: f32 = 3.0;
 
 #[derive(Debug)]
 pub struct PlayerPhysics {
+    /// Whose keys move this streamer
+    player_number: PlayerNumber,
     velocity: Point2<f32>,
     affected_by_gravity: bool,
-    player_hit_object: Sender<Chatter>,
-    heart_sound: audio::Source,
-    jump_sound: audio::Source,
-    hit_sound: audio::Source,
+    events: Sender<GameEvent>,
+    config: PlayerConfig,
 }
 
 impl PlayerPhysics {
-    pub fn new(context: &mut Context, player_hit_object: Sender<Chatter>) -> PlayerPhysics {
+    pub fn new(
+        player_number: PlayerNumber,
+        events: Sender<GameEvent>,
+        config: PlayerConfig,
+    ) -> PlayerPhysics {
         PlayerPhysics {
+            player_number,
             velocity: Point2::new(0.0, 0.0),
             affected_by_gravity: true,
-            player_hit_object,
-            heart_sound: audio::Source::new(context, "/phaserUp7.ogg").unwrap(),
-            jump_sound: audio::Source::new(context, "/phaseJump1.ogg").unwrap(),
-            hit_sound: audio::Source::new(context, "/zap2.ogg").unwrap(),
+            events,
+            config,
         }
     }
 
-    fn handle_input(&mut self, context: &mut Context) {
-        if input::keyboard::is_key_pressed(context, KeyCode::A) {
-            self.velocity.x -= MOVE_FORCE;
-        } else if input::keyboard::is_key_pressed(context, KeyCode::S)
-            || input::keyboard::is_key_pressed(context, KeyCode::D)
-        {
-            self.velocity.x += MOVE_FORCE;
+    fn handle_input(&mut self, input: &InputState, dt: f32) -> Result<()> {
+        let input = input.for_player(self.player_number);
+        if input.left {
+            self.velocity.x -= self.config.move_force * dt;
+        } else if input.right {
+            self.velocity.x += self.config.move_force * dt;
         }
 
-        if input::keyboard::is_key_pressed(context, KeyCode::Space) && self.on_ground() {
-            self.jump_sound.play().unwrap();
-            self.velocity.y += JUMP_FORCE;
+        if input.jump && self.on_ground() {
+            self.events.send(GameEvent::PlayerJumped {
+                player_number: self.player_number,
+            })?;
+            self.velocity.y += self.config.jump_force;
             self.affected_by_gravity = true;
         }
+
+        Ok(())
     }
 
     fn on_ground(&self) -> bool {
@@ -73,62 +82,85 @@ impl PlayerPhysics {
 
     fn get_colliding_with(
         &self,
-        collidable_game_objects: &[GameObject],
-        location: &Rect,
-    ) -> Option<GameObject> {
-        for other_game_object in collidable_game_objects {
-            if other_game_object.location.overlaps(location)
-                && other_game_object.my_type != GameObjectType::Player
-            {
-                return Some(other_game_object.clone());
-            }
-        }
-        None
+        broadphase: &Broadphase,
+        hitbox: &PlacedHitbox,
+    ) -> Option<Collider> {
+        broadphase
+            .objects_touching(hitbox)
+            .into_iter()
+            .find(|collider| collider.my_type != GameObjectType::Player)
+            .cloned()
+    }
+
+    /// Falling onto the top of something, rather than running into its side
+    fn is_landing_on(&self, other_location: &Rect, location: &Rect) -> bool {
+        self.velocity.y > 0.0
+            && location.y + location.h <= other_location.y + other_location.h / STOMP_DEPTH
     }
 }
 
 impl PhysicsSystem for PlayerPhysics {
     fn update(
         &mut self,
+        dt: f32,
         location: &mut Rect,
         arena: (f32, f32),
         gravity_force: f32,
-        context: &mut Context,
-        collidable_game_objects: &[GameObject],
-        _rotation: &mut f32,
+        input: &InputState,
+        broadphase: &Broadphase,
+        hitbox: &Hitbox,
+        rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
     ) -> Result<()> {
-        self.handle_input(context);
+        self.handle_input(input, dt)?;
         self.stay_in_arena(location, arena);
 
-        if let Some(game_object) = self.get_colliding_with(collidable_game_objects, location) {
-            if let Some(player_life_system) = life_system.as_deref_mut() {
+        if let Some(game_object) =
+            self.get_colliding_with(broadphase, &hitbox.place(*location, *rotation))
+        {
+            if game_object.my_type == GameObjectType::Boss
+                && self.is_landing_on(&game_object.location, location)
+            {
+                // bounce off so one jump is one stomp
+                self.velocity.y = self.config.jump_force;
+                self.affected_by_gravity = true;
+                self.events.send(GameEvent::BossStomped)?;
+            } else if let Some(player_life_system) = life_system.as_deref_mut() {
                 if GameObjectType::Heart == game_object.my_type {
-                    player_life_system.gain_life();
-                    self.heart_sound.play().unwrap();
+                    if player_life_system.gain_life() {
+                        self.events.send(GameEvent::PlayerHealed {
+                            player_number: self.player_number,
+                            chatter: game_object.chatter,
+                        })?;
+                    } else if player_life_system.hit() {
+                        self.events.send(GameEvent::PlayerHurtByHeart {
+                            player_number: self.player_number,
+                            chatter: game_object.chatter,
+                        })?;
+                    }
                 } else if player_life_system.hit() {
-                    self.hit_sound.play().unwrap();
-                    let chatter = if let Some(chatter) = game_object.chatter {
-                        chatter
-                    } else {
-                        Chatter::new(DEFAULT_CHATTER_NAME.to_owned(), (255, 255, 255), false)
-                    };
-                    self.player_hit_object.send(chatter)?;
+                    // nobody gets the points for running into the boss before chat has steered it
+                    let is_boss = game_object.my_type == GameObjectType::Boss;
+                    let chatter = game_object.chatter.or_else(|| {
+                        (!is_boss).then(|| {
+                            Chatter::new(DEFAULT_CHATTER_NAME.to_owned(), (255, 255, 255), false)
+                        })
+                    });
+                    self.events.send(GameEvent::PlayerHit {
+                        player_number: self.player_number,
+                        chatter,
+                    })?;
                 }
             }
         }
 
         if self.affected_by_gravity {
-            self.velocity.y += gravity_force;
+            self.velocity.y += gravity_force * dt;
         }
-        location.x += self.velocity.x;
-        location.y += self.velocity.y;
+        location.x += self.velocity.x * dt;
+        location.y += self.velocity.y * dt;
 
-        if self.velocity.x != 0.0 {
-            let opposite_velocity = self.velocity.x * -1.0;
-            let speed_decrease = opposite_velocity * FRICTION;
-            self.velocity.x += speed_decrease
-        }
+        self.velocity.x *= (-self.config.friction * dt).exp();
 
         Ok(())
     }
@@ -1,38 +1,42 @@
 use eyre::Result;
-use ggez::{graphics::Rect, Context};
+use ggez::graphics::Rect;
+use ggez::nalgebra::Point2;
 
-use crate::{game_object::GameObject, game_object_type::GameObjectType, life_system::LifeSystem};
+use crate::{
+    broadphase::Broadphase, game_object_type::GameObjectType, hitbox::Hitbox,
+    input_state::InputState, life_system::LifeSystem,
+};
 
 use super::PhysicsSystem;
 
-const SNAKE_SPEED: f32 = 10.0;
-
 #[derive(Debug)]
 pub struct SnakePhysics {
     velocity_x: f32,
     velocity_y: f32,
     affected_by_gravity: bool,
+    speed: f32,
 }
 
 impl SnakePhysics {
-    pub fn new() -> Self {
+    pub fn new(speed: f32) -> Self {
         Self {
             velocity_x: 0.0,
             velocity_y: 0.0,
             affected_by_gravity: true,
+            speed,
         }
     }
 
-    fn set_x_velocity(&mut self, collidable_game_objects: &[GameObject], sprite: &Rect) {
-        let player = collidable_game_objects
-            .iter()
-            .find(|game_object| game_object.my_type == GameObjectType::Player);
+    /// Heads for the closest streamer
+    fn set_x_velocity(&mut self, broadphase: &Broadphase, sprite: &Rect) {
+        let center = Point2::new(sprite.x + sprite.w / 2.0, sprite.y + sprite.h / 2.0);
+        let player = broadphase.nearest_of_type(&GameObjectType::Player, center);
 
         if let Some(player) = player {
             if player.location.x < sprite.x {
-                self.velocity_x = -SNAKE_SPEED;
+                self.velocity_x = -self.speed;
             } else {
-                self.velocity_x = SNAKE_SPEED;
+                self.velocity_x = self.speed;
             }
         }
     }
@@ -41,27 +45,29 @@ impl SnakePhysics {
 impl PhysicsSystem for SnakePhysics {
     fn update(
         &mut self,
+        dt: f32,
         sprite: &mut Rect,
         screen_size: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
-        collidable_game_objects: &[GameObject],
+        _input: &InputState,
+        broadphase: &Broadphase,
+        _hitbox: &Hitbox,
         rotation: &mut f32,
         _life_system: &mut Option<Box<dyn LifeSystem>>,
     ) -> Result<()> {
         if self.affected_by_gravity {
-            self.velocity_y += gravity_force;
+            self.velocity_y += gravity_force * dt;
             *rotation = std::f32::consts::FRAC_PI_3 + 0.3;
         }
 
-        sprite.y += self.velocity_y;
-        sprite.x += self.velocity_x;
+        sprite.y += self.velocity_y * dt;
+        sprite.x += self.velocity_x * dt;
 
         if sprite.y + sprite.h > screen_size.1 {
             sprite.y = screen_size.1 - sprite.h;
             self.velocity_y = 0.0;
             self.affected_by_gravity = false;
-            self.set_x_velocity(collidable_game_objects, sprite);
+            self.set_x_velocity(broadphase, sprite);
             if self.velocity_x < 0.0 {
                 *rotation = std::f32::consts::PI;
             } else {
@@ -1,20 +1,28 @@
 use super::PhysicsSystem;
-use crate::{life_system::LifeSystem, GameObject};
+use crate::{
+    broadphase::Broadphase, hitbox::Hitbox, input_state::InputState, life_system::LifeSystem,
+};
 use eyre::Result;
 use ggez::graphics::Rect;
-use ggez::Context;
+use rand::Rng;
 
 #[derive(Debug)]
 pub struct SwordPhysics {
     velocity_x: f32,
     velocity_y: f32,
+    bounce_velocity_x: f32,
+    bounciness: f32,
 }
 
 impl SwordPhysics {
-    pub fn new() -> SwordPhysics {
+    /// The sideways velocity of the first bounce is picked up front so that
+    /// the simulation's random number generator doesn't need to be passed into `update`
+    pub fn new(max_bounce_speed: f32, bounciness: f32, rng: &mut impl Rng) -> SwordPhysics {
         SwordPhysics {
             velocity_x: 0.0,
             velocity_y: 0.0,
+            bounce_velocity_x: rng.gen::<f32>() * max_bounce_speed,
+            bounciness,
         }
     }
 
@@ -30,27 +38,29 @@ impl SwordPhysics {
 impl PhysicsSystem for SwordPhysics {
     fn update(
         &mut self,
+        dt: f32,
         location: &mut Rect,
         screen_size: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
-        _collidable_game_objects: &[GameObject],
+        _input: &InputState,
+        _broadphase: &Broadphase,
+        _hitbox: &Hitbox,
         rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
     ) -> Result<()> {
-        self.velocity_y += gravity_force;
-        location.y += self.velocity_y;
-        location.x += self.velocity_x;
+        self.velocity_y += gravity_force * dt;
+        location.y += self.velocity_y * dt;
+        location.x += self.velocity_x * dt;
         *rotation = self.calculate_rotation();
 
         if let Some(sword_life_system) = life_system.as_deref_mut() {
             if sword_life_system.get_lives_left() > 1 {
                 if location.y + location.h > screen_size.1 {
                     location.y = screen_size.1 - location.h;
-                    self.velocity_y *= -0.9;
+                    self.velocity_y *= -self.bounciness;
 
                     if self.is_first_fall() {
-                        self.velocity_x = rand::random::<f32>() * 15.0;
+                        self.velocity_x = self.bounce_velocity_x;
                     }
 
                     sword_life_system.hit();
@@ -73,4 +83,24 @@ impl PhysicsSystem for SwordPhysics {
     fn get_velocity_x(&self) -> f32 {
         self.velocity_x
     }
+
+    /// Always away from the other object, so that swords that stay touching don't flip back
+    /// and forth every tick
+    fn bounce_off(&mut self, location: &Rect, other: &Rect) {
+        let speed = if self.is_first_fall() {
+            self.bounce_velocity_x.abs()
+        } else {
+            self.velocity_x.abs()
+        };
+        let center_x = location.x + location.w / 2.0;
+        let other_center_x = other.x + other.w / 2.0;
+        // swords dropped into the same column split up with the higher one heading left
+        let away =
+            if center_x < other_center_x || (center_x == other_center_x && location.y < other.y) {
+                -1.0
+            } else {
+                1.0
+            };
+        self.velocity_x = speed * away;
+    }
 }
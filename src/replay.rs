@@ -0,0 +1,144 @@
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use eyre::{bail, Result};
+use ggez::{filesystem, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::chat_transport::IncomingChat;
+use crate::config::Config;
+use crate::input_state::InputState;
+use crate::object_catalog::ObjectCatalog;
+
+/// Version 1 replays were recorded with speeds in pixels per tick
+const REPLAY_VERSION: u32 = 2;
+pub const REPLAYS_DIRECTORY: &str = "/replays";
+
+/// A chat message exactly as it reached the game, with the tick it was handled in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayChatMessage {
+    pub tick: u64,
+    pub name: String,
+    pub color: (u8, u8, u8),
+    pub subscriber: bool,
+    /// Recordings from before `!gts` commands didn't keep badges
+    #[serde(default)]
+    pub badges: Vec<String>,
+    #[serde(default)]
+    pub bits: u64,
+    #[serde(default)]
+    pub custom_reward_id: Option<String>,
+    pub message: String,
+}
+
+impl ReplayChatMessage {
+    pub fn new(tick: u64, chat: IncomingChat) -> ReplayChatMessage {
+        ReplayChatMessage {
+            tick,
+            name: chat.chatter_name().to_owned(),
+            color: chat.color,
+            subscriber: chat.subscriber,
+            badges: chat.badges,
+            bits: chat.bits,
+            custom_reward_id: chat.custom_reward_id,
+            message: chat.message,
+        }
+    }
+
+    /// What the chatter paid with, `cheer` or `redemption`, `None` for ordinary messages
+    pub fn payment(&self) -> Option<&'static str> {
+        if self.custom_reward_id.is_some() {
+            Some("redemption")
+        } else if self.bits > 0 {
+            Some("cheer")
+        } else {
+            None
+        }
+    }
+}
+
+/// Everything needed to play a session again: the seed, the config, the objects,
+/// the streamer's input for every tick and every chat message that was turned into a command.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    pub seed: u64,
+    /// Recordings from before the config file existed were played with the defaults
+    #[serde(default)]
+    pub config: Config,
+    /// Recordings from before the object catalog existed only had the built in objects
+    #[serde(default)]
+    pub catalog: ObjectCatalog,
+    inputs: Vec<InputState>,
+    chat_messages: Vec<ReplayChatMessage>,
+    #[serde(skip)]
+    next_chat_message: usize,
+    /// Every save during a session goes to the same file
+    #[serde(skip)]
+    started_at: u64,
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: Config, catalog: ObjectCatalog) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            config,
+            catalog,
+            inputs: vec![],
+            chat_messages: vec![],
+            next_chat_message: 0,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |started_at| started_at.as_secs()),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Replay> {
+        let file = File::open(path)?;
+        let replay: Replay = serde_json::from_reader(BufReader::new(file))?;
+        if replay.version != REPLAY_VERSION {
+            bail!(
+                "replay file is version {}, but this game can only play version {}",
+                replay.version,
+                REPLAY_VERSION
+            );
+        }
+        Ok(replay)
+    }
+
+    /// Saves into the replays folder next to the high scores, returning the name of the file.
+    /// Saving again overwrites the file, so a session is kept in a single replay.
+    pub fn save(&self, context: &mut Context) -> Result<String> {
+        filesystem::create_dir(context, REPLAYS_DIRECTORY)?;
+        let file_name = format!("{}/replay_{}.json", REPLAYS_DIRECTORY, self.started_at);
+        let mut replay_file = filesystem::create(context, &file_name)?;
+        replay_file.write_all(serde_json::to_string(self)?.as_bytes())?;
+        Ok(file_name)
+    }
+
+    /// Record one tick, this must be called for every tick in order
+    pub fn record(&mut self, input: InputState, chat_messages: &[ReplayChatMessage]) {
+        self.inputs.push(input);
+        self.chat_messages.extend_from_slice(chat_messages);
+    }
+
+    /// The recorded input, once the recording runs out the streamer stops pressing anything
+    pub fn get_input(&self, tick: u64) -> InputState {
+        self.inputs.get(tick as usize).copied().unwrap_or_default()
+    }
+
+    /// The chat messages that were handled during `tick`, ticks must be asked for in order
+    pub fn take_chat_messages(&mut self, tick: u64) -> Vec<ReplayChatMessage> {
+        let first = self.next_chat_message;
+        while let Some(chat_message) = self.chat_messages.get(self.next_chat_message) {
+            if chat_message.tick > tick {
+                break;
+            }
+            self.next_chat_message += 1;
+        }
+        self.chat_messages[first..self.next_chat_message].to_vec()
+    }
+}
@@ -1,7 +1,9 @@
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RunningState {
     StartingSoon,
     Playing,
+    /// Stopped by a `!gts pause` from chat, nothing moves until `!gts resume`
+    Paused,
     PlayerWon,
     ChatWon,
 }
@@ -9,7 +11,7 @@ pub enum RunningState {
 impl RunningState {
     pub fn is_game_over(&self) -> bool {
         match self {
-            RunningState::Playing | RunningState::StartingSoon => false,
+            RunningState::Playing | RunningState::Paused | RunningState::StartingSoon => false,
             RunningState::PlayerWon | RunningState::ChatWon => true,
         }
     }
@@ -0,0 +1,1148 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ggez::graphics::Rect;
+use ggez::nalgebra::Point2;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::boss::{BossAction, BossTally};
+use crate::broadphase::Broadphase;
+use crate::chatter::Chatter;
+use crate::chatter_stats::ChatterStats;
+use crate::command::Command;
+use crate::config::Config;
+use crate::control_command::ControlCommand;
+use crate::drop_limiter::DropLimiter;
+use crate::game_event::{EventBus, GameEvent};
+use crate::game_object::GameObject;
+use crate::game_object_type::GameObjectType;
+use crate::hitbox::PixelMask;
+use crate::input_state::{InputState, PlayerNumber};
+use crate::interface::DROP_ZONE_HEIGHT;
+use crate::life_system::{BossLifeSystem, PlayerLifeSystem};
+use crate::object_catalog::{InteractionOutcome, ObjectCatalog};
+use crate::physics::{BossPhysics, PlayerPhysics};
+use crate::replay::ReplayChatMessage;
+use crate::running_state::RunningState;
+use crate::team::{Team, Teams};
+use crate::vote::{Vote, VoteTally};
+
+/// The size of a single frame in the player sprite sheets
+const PLAYER_FRAME_SIZE: (f32, f32) = (16.0, 16.0);
+pub const PLAYER_SCALE: f32 = 4.0;
+
+/// All of the game rules, without any window, audio or keyboard.
+/// The simulation is advanced one fixed tick at a time, so it can be run and tested headless.
+pub struct Simulation {
+    send_to_chat: Sender<String>,
+    arena_size: (f32, f32),
+    game_objects: Vec<GameObject>,
+    /// Everything that happens in the game goes through here, on its way to the scores, stats,
+    /// chat and whoever else subscribed
+    events: EventBus,
+    running_state: RunningState,
+    ticks: u64,
+    ticks_left_in_state: u32,
+    scores: HashMap<String, u128>,
+    session_scores: HashMap<String, u128>,
+    round_stats: HashMap<String, ChatterStats>,
+    /// Lowercase names of chatters whose drops are ignored for the rest of the session
+    banned: HashSet<String>,
+    drop_limiter: DropLimiter,
+    vote_tally: VoteTally,
+    /// Who is on which team, for the whole session
+    teams: Teams,
+    /// Points scored this round by each team
+    team_scores: BTreeMap<Team, u128>,
+    boss_tally: BossTally,
+    /// Cheers and redemptions from while the round wasn't being played, handled once it is
+    held_chat_messages: Vec<ReplayChatMessage>,
+    /// Tells this round's boss what chat decided
+    send_boss_action: Option<Sender<BossAction>>,
+    round: u32,
+    /// Counts up from 1 in endless mode, 0 before the round is being played
+    wave: u32,
+    /// Ticks spent playing this round, without the pauses
+    ticks_played: u64,
+    seed: u64,
+    rng: StdRng,
+    config: Config,
+    /// The config with this wave's difficulty, the same as `config` outside of endless mode
+    wave_config: Config,
+    catalog: ObjectCatalog,
+    /// By object name, for the catalog objects with `pixel_mask` whose sprites have been loaded
+    pixel_masks: HashMap<String, Arc<PixelMask>>,
+}
+
+impl Simulation {
+    /// All randomness in the game comes from `seed`, so the same seed and inputs play the same game
+    pub fn new(
+        send_to_chat: Sender<String>,
+        arena_size: (f32, f32),
+        seed: u64,
+        config: Config,
+        catalog: ObjectCatalog,
+    ) -> Simulation {
+        let mut simulation = Simulation {
+            send_to_chat,
+            arena_size,
+            game_objects: vec![],
+            events: EventBus::default(),
+            running_state: RunningState::StartingSoon,
+            ticks: 0,
+            ticks_left_in_state: 0,
+            scores: HashMap::new(),
+            session_scores: HashMap::new(),
+            round_stats: HashMap::new(),
+            banned: HashSet::new(),
+            drop_limiter: DropLimiter::default(),
+            vote_tally: VoteTally::default(),
+            teams: Teams::default(),
+            team_scores: BTreeMap::new(),
+            boss_tally: BossTally::default(),
+            held_chat_messages: vec![],
+            send_boss_action: None,
+            round: 0,
+            wave: 0,
+            ticks_played: 0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            wave_config: config.clone(),
+            config,
+            catalog,
+            pixel_masks: HashMap::new(),
+        };
+        simulation.start_round();
+        simulation
+    }
+
+    /// Advance the game by a single tick of `1 / framerate_target` seconds.
+    /// Commands are only handled while the game is being played.
+    pub fn tick(&mut self, input: &InputState, commands: Vec<Command>) {
+        self.ticks += 1;
+
+        if input.start_next_round {
+            self.start_next_round_early();
+        }
+
+        match self.running_state {
+            RunningState::StartingSoon => {
+                self.ticks_left_in_state = self.ticks_left_in_state.saturating_sub(1);
+                if self.ticks_left_in_state == 0 {
+                    self.start_playing();
+                }
+            }
+            RunningState::Playing => {
+                commands
+                    .into_iter()
+                    .for_each(|command| self.limit_command(command));
+                self.close_vote();
+                self.command_boss();
+
+                self.ticks_played += 1;
+                self.ticks_left_in_state = self.ticks_left_in_state.saturating_sub(1);
+                if self.ticks_left_in_state == 0 {
+                    if self.config.endless.enabled {
+                        self.start_wave(self.wave + 1);
+                    } else {
+                        self.end_game(RunningState::PlayerWon);
+                        return;
+                    }
+                }
+
+                self.update_game_objects(input);
+                self.dispatch_events();
+
+                if self.players().next().is_none() {
+                    self.end_game(RunningState::ChatWon);
+                } else if self.config.boss.enabled && self.boss_health().unwrap_or(0) == 0 {
+                    self.send_message("The streamer beat the boss!".to_owned());
+                    self.end_game(RunningState::PlayerWon);
+                }
+            }
+            RunningState::Paused => {}
+            RunningState::ChatWon | RunningState::PlayerWon => {
+                if self.config.game.session_mode {
+                    self.ticks_left_in_state = self.ticks_left_in_state.saturating_sub(1);
+                    if self.ticks_left_in_state == 0 {
+                        self.start_round();
+                    }
+                }
+            }
+        }
+        self.dispatch_events();
+    }
+
+    /// Turn chat messages into commands, replying to chat when a command can't be understood.
+    /// This uses the simulation's random number generator, so it has to happen in tick order.
+    /// Drops only happen while the round is being played, cheers and redemptions from before
+    /// then are held until it is so nobody pays for nothing.
+    pub fn create_commands(&mut self, chat_messages: &[ReplayChatMessage]) -> Vec<Command> {
+        let mut commands = vec![];
+        let playing = self.running_state == RunningState::Playing;
+        let held_chat_messages = if playing {
+            std::mem::take(&mut self.held_chat_messages)
+        } else {
+            vec![]
+        };
+        for chat_message in held_chat_messages.iter().chain(chat_messages) {
+            if let Some(control_command) = ControlCommand::parse(&chat_message.message) {
+                if ControlCommand::is_allowed(&chat_message.badges) {
+                    match control_command {
+                        Ok(control_command) => {
+                            self.handle_control_command(control_command, &chat_message.name)
+                        }
+                        Err(error) => self.send_message(error.to_string()),
+                    }
+                }
+                continue;
+            }
+            if self.banned.contains(&chat_message.name.to_lowercase()) {
+                if let Some(payment) = chat_message.payment() {
+                    self.send_message(format!(
+                        "@{} you can't drop anything for the rest of the session, so your {} didn't drop anything",
+                        chat_message.name, payment
+                    ));
+                }
+                continue;
+            }
+            // teams can be picked in every state, the splash screen is when most chatters do
+            if self.config.teams.enabled {
+                if let Some(team_name) = Team::parse_join(&chat_message.message) {
+                    self.join_team(&chat_message.name, team_name);
+                    continue;
+                }
+            }
+            if !playing {
+                if chat_message.payment().is_some() {
+                    self.hold_chat_message(chat_message.clone());
+                }
+                continue;
+            }
+
+            let chatter = Chatter::new(
+                chat_message.name.clone(),
+                chat_message.color,
+                chat_message.subscriber,
+            );
+            if let Some(drop) = self
+                .config
+                .premium
+                .find(chat_message.bits, chat_message.custom_reward_id.as_deref())
+            {
+                let announcement = if chat_message.custom_reward_id.is_some() {
+                    format!("{} redeemed {}!", chat_message.name, drop.name)
+                } else {
+                    format!(
+                        "{} cheered {} bits for {}!",
+                        chat_message.name, chat_message.bits, drop.name
+                    )
+                };
+                commands.extend(Command::new_premium(
+                    drop,
+                    &chat_message.message,
+                    chatter,
+                    &self.config,
+                    &self.catalog,
+                    &mut self.rng,
+                ));
+                self.send_message(announcement);
+                continue;
+            }
+            if self.config.boss.enabled {
+                if let Some(action) = BossAction::parse(&chat_message.message) {
+                    let window_ticks = self
+                        .config
+                        .game
+                        .seconds_to_ticks(self.config.boss.window_seconds);
+                    self.boss_tally
+                        .cast(chatter, action, self.ticks, window_ticks);
+                }
+                continue;
+            }
+            if self.config.voting.enabled {
+                self.cast_vote(chatter, &chat_message.message);
+                continue;
+            }
+            match Command::new(
+                &chat_message.message,
+                chatter,
+                &self.config,
+                &self.catalog,
+                &mut self.rng,
+            ) {
+                Err(error) => {
+                    if let Err(error) = self.send_to_chat.send(error.to_owned()) {
+                        eprintln!("error sending command error to chat: {}", error);
+                    }
+                }
+                Ok(Some(command)) => commands.push(command),
+                Ok(None) => (),
+            }
+        }
+        commands
+    }
+
+    /// Keeps a cheer or redemption for the next round, unless there won't be one
+    fn hold_chat_message(&mut self, chat_message: ReplayChatMessage) {
+        let payment = chat_message.payment().unwrap_or("message");
+        if self.running_state.is_game_over() && !self.config.game.session_mode {
+            self.send_message(format!(
+                "@{} the game is over, so your {} couldn't drop anything",
+                chat_message.name, payment
+            ));
+        } else {
+            self.send_message(format!(
+                "@{} thanks! Your {} drops as soon as the round is being played",
+                chat_message.name, payment
+            ));
+            self.held_chat_messages.push(chat_message);
+        }
+    }
+
+    pub fn running_state(&self) -> RunningState {
+        self.running_state
+    }
+
+    pub fn game_objects(&self) -> &[GameObject] {
+        &self.game_objects
+    }
+
+    pub fn game_objects_mut(&mut self) -> &mut [GameObject] {
+        &mut self.game_objects
+    }
+
+    /// Points scored this round
+    pub fn scores(&self) -> &HashMap<String, u128> {
+        &self.scores
+    }
+
+    /// Points scored in every round that has finished since the game was launched
+    pub fn session_scores(&self) -> &HashMap<String, u128> {
+        &self.session_scores
+    }
+
+    /// What each chatter did this round, `rounds_played` and `rounds_won_by_chat` are filled in
+    /// once the round is over
+    pub fn round_stats(&self) -> &HashMap<String, ChatterStats> {
+        &self.round_stats
+    }
+
+    /// The session scores from highest to lowest
+    pub fn get_session_leaderboard(&self) -> Vec<(String, u128)> {
+        let mut leaderboard: Vec<(String, u128)> = self
+            .session_scores
+            .iter()
+            .map(|(name, score)| (name.to_owned(), *score))
+            .collect();
+        leaderboard.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        leaderboard
+    }
+
+    /// Counts up from 1 every time a new round starts
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// The number of ticks that have run so far, which is also the number of the next tick
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The objects chat can drop
+    pub fn catalog(&self) -> &ObjectCatalog {
+        &self.catalog
+    }
+
+    /// Objects named `object_name` dropped from now on only collide where `pixel_mask` is solid.
+    /// The masks come from the sprites, so replays only play back the same with the same masks.
+    pub fn set_pixel_mask(&mut self, object_name: &str, pixel_mask: PixelMask) {
+        self.pixel_masks
+            .insert(object_name.to_owned(), Arc::new(pixel_mask));
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The random number generator that every random choice in the game must be made with
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// How long the simulation has been running, counted in ticks rather than wall clock time
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(self.ticks as f64 / self.config.game.framerate_target as f64)
+    }
+
+    pub fn player_lives_left(&self, player_number: PlayerNumber) -> u8 {
+        if let Some(player) = self.get_player(player_number) {
+            player.get_lives_left().unwrap_or(self.config.game.lives)
+        } else {
+            0
+        }
+    }
+
+    /// Gets every game event from now on, in the order the simulation handled them
+    pub fn subscribe(&mut self) -> Receiver<GameEvent> {
+        self.events.subscribe()
+    }
+
+    /// Take in an index like 3
+    /// which should return the middle x,y coordinates of the corresponding drop zone
+    pub fn get_column_coordinates_by_index(&self, index: u8) -> Point2<f32> {
+        let single_drop_zone_width = self.arena_size.0 / self.config.game.drop_zone_count as f32;
+        Point2::new(
+            index as f32 * single_drop_zone_width + single_drop_zone_width / 2.0,
+            DROP_ZONE_HEIGHT / 2.0,
+        )
+    }
+
+    /// `None` once that streamer is out of lives, or when they aren't playing
+    pub fn get_player(&self, player_number: PlayerNumber) -> Option<&GameObject> {
+        self.players()
+            .find(|player| player.player_number == Some(player_number))
+    }
+
+    /// The streamers who still have lives left
+    pub fn players(&self) -> impl Iterator<Item = &GameObject> {
+        self.game_objects
+            .iter()
+            .filter(|game_object| game_object.my_type == GameObjectType::Player)
+    }
+
+    pub fn get_highest_scorer(&self) -> Option<(String, u128)> {
+        self.scores
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|scorer| (scorer.0.to_owned(), *scorer.1))
+    }
+
+    /// Drops only make it into the arena when the chatter's cooldowns are over and
+    /// there's room for more enemies
+    fn limit_command(&mut self, command: Command) {
+        // paid for, so it isn't held back and doesn't start any cooldowns
+        if command.premium.is_some() {
+            self.handle_command(command);
+            return;
+        }
+        let live_enemies = self
+            .game_objects
+            .iter()
+            .filter(|game_object| game_object.my_type == GameObjectType::Enemy)
+            .count();
+        match self
+            .drop_limiter
+            .check(&command, self.ticks, live_enemies, &self.wave_config)
+        {
+            Ok(()) => {
+                self.drop_limiter.record(&command, self.ticks);
+                self.handle_command(command);
+            }
+            Err(refusal) => {
+                if self
+                    .drop_limiter
+                    .should_reply(self.ticks, &self.wave_config)
+                {
+                    self.send_message(refusal.to_message(&command.chatter.name));
+                }
+            }
+        }
+    }
+
+    /// In voting mode every drop command is a vote, anything else chat says is ignored
+    fn cast_vote(&mut self, chatter: Chatter, message: &str) {
+        match Vote::parse(message, &self.catalog, self.config.game.drop_zone_count) {
+            Some(Ok(vote)) => {
+                let window_ticks = self
+                    .config
+                    .game
+                    .seconds_to_ticks(self.config.voting.window_seconds);
+                self.vote_tally
+                    .cast(chatter, vote, self.ticks, window_ticks);
+            }
+            Some(Err(error)) => self.send_message(error.to_owned()),
+            None => (),
+        }
+    }
+
+    /// Drops whatever chat voted for once the vote's window is over
+    fn close_vote(&mut self) {
+        let result = match self.vote_tally.close(self.ticks) {
+            Some(result) => result,
+            None => return,
+        };
+        let object = match self.catalog.get(&result.object) {
+            Some(object) => object.clone(),
+            None => return,
+        };
+        let count = self.config.voting.object_count(result.voters);
+        let column = result
+            .column
+            .unwrap_or_else(|| self.rng.gen_range(0, self.config.game.drop_zone_count));
+        self.send_message(format!(
+            "The vote is in: {} x{} into column {}, {} voted!",
+            object.name, count, column, result.voters
+        ));
+        for _ in 0..count {
+            self.handle_command(Command {
+                object: object.clone(),
+                id: column,
+                chatter: result.leader.clone(),
+                premium: None,
+            });
+        }
+    }
+
+    /// Points scored by each team this round, highest first. Empty outside of team mode.
+    pub fn team_scores(&self) -> Vec<(Team, u128)> {
+        if !self.config.teams.enabled {
+            return vec![];
+        }
+        let mut team_scores: Vec<(Team, u128)> = Team::ALL
+            .iter()
+            .map(|team| (*team, self.team_scores.get(team).copied().unwrap_or(0)))
+            .collect();
+        team_scores.sort_by_key(|(_team, score)| Reverse(*score));
+        team_scores
+    }
+
+    /// The team with the most points this round, `None` on a tie or outside of team mode
+    pub fn winning_team(&self) -> Option<Team> {
+        match self.team_scores().as_slice() {
+            [(winner, winning_score), (_loser, losing_score)] if winning_score > losing_score => {
+                Some(*winner)
+            }
+            _ => None,
+        }
+    }
+
+    /// Does what most of chat asked the boss to do once the window is over
+    fn command_boss(&mut self) {
+        let (action, mut leader) = match self.boss_tally.decide(self.ticks) {
+            Some(decision) => decision,
+            None => return,
+        };
+        if self.config.teams.enabled {
+            leader.team = Some(self.teams.get_or_assign(&leader.name));
+        }
+        let boss_location = match self.get_boss_mut() {
+            Some(boss) => {
+                // whatever the boss does until the next decision is to the leader's credit
+                boss.chatter = Some(leader.clone());
+                boss.location
+            }
+            None => return,
+        };
+
+        if action == BossAction::Spit {
+            self.spit(boss_location, leader);
+        } else if let Some(send_boss_action) = &self.send_boss_action {
+            if let Err(error) = send_boss_action.send(action) {
+                eprintln!("error sending action to the boss: {}", error);
+            }
+        }
+    }
+
+    /// Throws the spit object out of the side of the boss that faces the closest streamer
+    fn spit(&mut self, boss_location: Rect, leader: Chatter) {
+        let object = match self.catalog.get(&self.config.boss.spit_object) {
+            Some(object) => object.clone(),
+            None => return,
+        };
+        let boss_x = boss_location.x + boss_location.w / 2.0;
+        let player_x = self
+            .players()
+            .map(|player| player.location.x + player.location.w / 2.0)
+            .min_by(|a, b| (a - boss_x).abs().total_cmp(&(b - boss_x).abs()))
+            .unwrap_or(0.0);
+        let (width, _height) = object.get_size();
+        let x = if player_x < boss_x {
+            boss_location.x - width
+        } else {
+            boss_location.x + boss_location.w + width
+        };
+        let spit_location = Point2::new(x, boss_location.y + boss_location.h / 3.0);
+        let command = Command {
+            object,
+            id: 0,
+            chatter: leader,
+            premium: None,
+        };
+        let spit = command.handle(spit_location, &self.wave_config, &mut self.rng);
+        self.add_dropped_object(spit, None);
+    }
+
+    /// `dropped_by` gets the credit for the drop, so it is `None` for the boss's spit
+    fn add_dropped_object(&mut self, mut game_object: GameObject, dropped_by: Option<Chatter>) {
+        let pixel_mask = game_object
+            .object_name
+            .as_ref()
+            .and_then(|object_name| self.pixel_masks.get(object_name));
+        if let Some(pixel_mask) = pixel_mask {
+            game_object.hitbox.mask = Some(Arc::clone(pixel_mask));
+        }
+        self.events.publish(GameEvent::ObjectSpawned {
+            object_name: game_object.object_name.clone().unwrap_or_default(),
+            dropped_by,
+        });
+        self.game_objects.push(game_object);
+    }
+
+    fn get_boss_mut(&mut self) -> Option<&mut GameObject> {
+        self.game_objects
+            .iter_mut()
+            .find(|game_object| game_object.my_type == GameObjectType::Boss)
+    }
+
+    /// How much health the boss has left, `None` when there isn't one
+    pub fn boss_health(&self) -> Option<u8> {
+        self.game_objects
+            .iter()
+            .find(|game_object| game_object.my_type == GameObjectType::Boss)
+            .and_then(GameObject::get_lives_left)
+    }
+
+    /// The wave being survived, `None` outside of endless mode
+    pub fn wave(&self) -> Option<u32> {
+        if self.config.endless.enabled {
+            Some(self.wave)
+        } else {
+            None
+        }
+    }
+
+    /// How long the streamer has been playing this round, pauses don't count
+    pub fn survival_time(&self) -> Duration {
+        Duration::from_secs_f64(self.ticks_played as f64 / self.config.game.framerate_target as f64)
+    }
+
+    /// Everything gets a little harder, the wave lasts `endless.wave_seconds`
+    fn start_wave(&mut self, wave: u32) {
+        self.wave = wave;
+        self.wave_config = self.config.for_wave(wave);
+        self.ticks_left_in_state = self
+            .config
+            .game
+            .duration_to_ticks(self.config.endless.wave_time());
+        if wave > 1 {
+            self.send_message(format!("Wave {}! Everything just got faster", wave));
+        }
+    }
+
+    /// The votes so far, `None` when the game isn't in voting mode
+    pub fn vote_tally(&self) -> Option<&VoteTally> {
+        if self.config.voting.enabled {
+            Some(&self.vote_tally)
+        } else {
+            None
+        }
+    }
+
+    fn join_team(&mut self, name: &str, team_name: &str) {
+        let message = match Team::parse(team_name) {
+            Some(team) => match self.teams.join(name, team) {
+                Ok(()) => format!("@{} joined the {} team", name, team.name()),
+                Err(current_team) => {
+                    format!("@{} is already on the {} team", name, current_team.name())
+                }
+            },
+            None => "Join a team with !join red or !join blue".to_owned(),
+        };
+        self.send_message(message);
+    }
+
+    /// Round points for the chatter, and for their team in team mode
+    fn add_points(&mut self, name: &str, points: u128) {
+        *self.scores.entry(name.to_owned()).or_insert(0) += points;
+        if let Some(team) = self.teams.get(name) {
+            *self.team_scores.entry(team).or_insert(0) += points;
+        }
+    }
+
+    fn handle_command(&mut self, mut command: Command) {
+        if self.config.teams.enabled {
+            // chatters who haven't picked a team are put on one with their first drop
+            command.chatter.team = Some(self.teams.get_or_assign(&command.chatter.name));
+        }
+        let chatter = command.chatter.clone();
+        let drop_zone_location = self.get_column_coordinates_by_index(command.id);
+        let game_object = command.handle(drop_zone_location, &self.wave_config, &mut self.rng);
+        self.add_dropped_object(game_object, Some(chatter));
+    }
+
+    fn handle_control_command(&mut self, control_command: ControlCommand, sent_by: &str) {
+        match control_command {
+            ControlCommand::Pause if self.running_state == RunningState::Playing => {
+                self.running_state = RunningState::Paused;
+                self.send_message(format!("{} paused the game", sent_by));
+            }
+            ControlCommand::Resume if self.running_state == RunningState::Paused => {
+                self.running_state = RunningState::Playing;
+                self.send_message(format!("{} resumed the game", sent_by));
+            }
+            ControlCommand::Start => self.start_next_round_early(),
+            ControlCommand::End
+                if matches!(
+                    self.running_state,
+                    RunningState::Playing | RunningState::Paused
+                ) =>
+            {
+                self.end_game(RunningState::PlayerWon);
+            }
+            ControlCommand::Ban(name) => {
+                self.send_message(format!(
+                    "{} can't drop anything for the rest of the session",
+                    name
+                ));
+                self.banned.insert(name);
+            }
+            ControlCommand::AddLives(lives) => {
+                let mut gave_lives = false;
+                let player_life_systems = self
+                    .game_objects
+                    .iter_mut()
+                    .filter(|game_object| game_object.my_type == GameObjectType::Player)
+                    .filter_map(|player| player.life_system.as_deref_mut());
+                for player_life_system in player_life_systems {
+                    // never more than `player.max_lives`, even from a moderator
+                    for _ in 0..lives {
+                        player_life_system.gain_life();
+                    }
+                    gave_lives = true;
+                }
+                if gave_lives {
+                    self.send_message(format!(
+                        "{} gave the streamer {} more lives",
+                        sent_by, lives
+                    ));
+                }
+            }
+            // pausing when not playing and so on does nothing
+            _ => {}
+        }
+    }
+
+    fn send_message(&self, message: String) {
+        if let Err(error) = self.send_to_chat.send(message) {
+            eprintln!("error sending message to chat: {}", error);
+        }
+    }
+
+    /// Hands out everything published since the last time, handling an event can publish more
+    fn dispatch_events(&mut self) {
+        loop {
+            let events = self.events.dispatch();
+            if events.is_empty() {
+                break;
+            }
+            for event in events {
+                self.handle_event(&event);
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &GameEvent) {
+        if let GameEvent::BossStomped = event {
+            let stomped = self
+                .get_boss_mut()
+                .and_then(|boss| boss.life_system.as_deref_mut())
+                .is_some_and(|life_system| life_system.hit());
+            if stomped {
+                let health_left = self.boss_health().unwrap_or(0);
+                self.events.publish(GameEvent::BossHurt { health_left });
+            }
+        }
+        self.keep_score(event);
+        self.keep_stats(event);
+        self.announce(event);
+    }
+
+    fn keep_score(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ObjectSpawned {
+                dropped_by: Some(chatter),
+                ..
+            } => self.add_points(&chatter.name, 1),
+            GameEvent::PlayerHit {
+                chatter: Some(chatter),
+                ..
+            } => self.add_points(&chatter.name, 10),
+            _ => {}
+        }
+    }
+
+    fn keep_stats(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ObjectSpawned {
+                object_name,
+                dropped_by: Some(chatter),
+            } => {
+                *self
+                    .round_stats
+                    .entry(chatter.name.clone())
+                    .or_default()
+                    .drops
+                    .entry(object_name.clone())
+                    .or_insert(0) += 1;
+            }
+            GameEvent::PlayerHit {
+                chatter: Some(chatter),
+                ..
+            } => {
+                self.round_stats
+                    .entry(chatter.name.clone())
+                    .or_default()
+                    .hits_landed += 1;
+            }
+            GameEvent::PlayerHealed {
+                chatter: Some(chatter),
+                ..
+            } => {
+                self.round_stats
+                    .entry(chatter.name.clone())
+                    .or_default()
+                    .hearts_given += 1;
+            }
+            GameEvent::PlayerHurtByHeart {
+                chatter: Some(chatter),
+                ..
+            } => {
+                self.round_stats
+                    .entry(chatter.name.clone())
+                    .or_default()
+                    .hearts_that_hurt += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn announce(&self, event: &GameEvent) {
+        match event {
+            GameEvent::PlayerHit {
+                chatter: Some(chatter),
+                ..
+            } => {
+                let message = match chatter.team {
+                    Some(team) => format!(
+                        "Hit! {} gets 10 points for the {} team",
+                        &chatter.name,
+                        team.name()
+                    ),
+                    None => format!("Hit! {} gets 10 points", &chatter.name),
+                };
+                self.send_message(message);
+            }
+            GameEvent::PlayerHurtByHeart {
+                chatter: Some(chatter),
+                ..
+            } => self.send_message(format!(
+                "Too much love! The streamer's lives were full, so {}'s heart hurt",
+                &chatter.name
+            )),
+            GameEvent::BossHurt { health_left } => self.send_message(format!(
+                "The streamer landed on the boss, {} health left!",
+                health_left
+            )),
+            GameEvent::RoundStarted { .. } => self.send_game_started_message(),
+            GameEvent::RoundEnded { winner, .. } => self.send_game_ended_message(*winner),
+            _ => {}
+        }
+    }
+
+    fn update_game_objects(&mut self, input: &InputState) {
+        let time_since_start = self.elapsed();
+        let dt = self.config.game.tick_seconds();
+        let arena_size = self.arena_size;
+        let gravity_force = self.wave_config.physics.gravity_force;
+
+        // everything collides with where the others were at the start of the tick
+        let broadphase = Broadphase::new(&self.game_objects);
+        self.handle_interactions(&broadphase);
+
+        self.game_objects.iter_mut().for_each(|game_object| {
+            if let Err(error) = game_object.update(
+                time_since_start,
+                dt,
+                arena_size,
+                gravity_force,
+                input,
+                &broadphase,
+            ) {
+                eprintln!("error running update: {}", error)
+            }
+        });
+
+        for game_object in &self.game_objects {
+            if game_object.is_alive() {
+                continue;
+            }
+            if let Some(reason) = game_object.death_reason() {
+                self.events.publish(GameEvent::ObjectDied {
+                    object_name: game_object.object_name.clone(),
+                    chatter: game_object.chatter.clone(),
+                    reason,
+                });
+            }
+        }
+        self.game_objects
+            .retain(|game_object| game_object.is_alive());
+    }
+
+    /// Dropped objects that touch each other, as listed in the catalog's interactions. Everything
+    /// is decided before anything changes, so both sides of a touch see the same arena.
+    fn handle_interactions(&mut self, broadphase: &Broadphase) {
+        let mut outcomes = vec![];
+        for (index, game_object) in self.game_objects.iter().enumerate() {
+            let object_name = match &game_object.object_name {
+                Some(object_name) if self.catalog.has_interactions(object_name) => object_name,
+                _ => continue,
+            };
+            for other in broadphase.objects_touching(&game_object.placed_hitbox()) {
+                let outcome = other
+                    .object_name
+                    .as_ref()
+                    .filter(|_| other.index != index)
+                    .and_then(|other_name| self.catalog.interaction(object_name, other_name));
+                if let Some(outcome) = outcome {
+                    outcomes.push((index, outcome, other));
+                }
+            }
+        }
+
+        for (index, outcome, other) in outcomes {
+            let game_object = &mut self.game_objects[index];
+            match outcome {
+                InteractionOutcome::Destroyed => {
+                    game_object.destroy(other.object_name.as_deref().unwrap_or_default())
+                }
+                InteractionOutcome::Bounce => game_object.bounce_off(&other.location),
+            }
+        }
+    }
+
+    /// Back to the splash screen with a fresh player, an empty arena and no points
+    fn start_round(&mut self) {
+        self.round += 1;
+        self.running_state = RunningState::StartingSoon;
+        self.ticks_left_in_state = self
+            .config
+            .game
+            .duration_to_ticks(self.config.game.splash_duration());
+        self.scores.clear();
+        self.round_stats.clear();
+        self.vote_tally.clear();
+        self.team_scores.clear();
+        self.boss_tally.clear();
+        self.wave = 0;
+        self.ticks_played = 0;
+        self.wave_config = self.config.clone();
+        // whatever happened in the last round doesn't count in this one
+        self.events.dispatch();
+        self.game_objects = self
+            .config
+            .coop
+            .player_numbers()
+            .iter()
+            .map(|player_number| self.create_player(*player_number))
+            .collect();
+        if self.config.boss.enabled {
+            if let Some(boss) = self.create_boss() {
+                self.game_objects.push(boss);
+            }
+        }
+
+        let message = format!("In {} seconds round {} of the Get the Streamer game will begin, you can play through chat with the commands on the right side of the game.", self.config.game.splash_duration_seconds, self.round);
+        if let Err(error) = self.send_to_chat.send(message) {
+            eprintln!("error sending splash message to chat: {}", error);
+        }
+    }
+
+    fn start_playing(&mut self) {
+        self.events
+            .publish(GameEvent::RoundStarted { round: self.round });
+        self.running_state = RunningState::Playing;
+        if self.config.endless.enabled {
+            self.start_wave(1);
+        } else {
+            self.ticks_left_in_state = self
+                .config
+                .game
+                .duration_to_ticks(self.config.game.game_time());
+        }
+    }
+
+    /// The streamer's hotkey, cuts the splash screen or the intermission short
+    fn start_next_round_early(&mut self) {
+        match self.running_state {
+            RunningState::StartingSoon => self.start_playing(),
+            RunningState::ChatWon | RunningState::PlayerWon if self.config.game.session_mode => {
+                self.start_round();
+                self.start_playing();
+            }
+            _ => {}
+        }
+    }
+
+    /// The second streamer starts a little to the right of the first
+    fn create_player(&self, player_number: PlayerNumber) -> GameObject {
+        let player_physics_system = PlayerPhysics::new(
+            player_number,
+            self.events.publisher(),
+            self.config.player.clone(),
+        );
+        let x = match player_number {
+            PlayerNumber::One => 250.0,
+            PlayerNumber::Two => 250.0 + PLAYER_FRAME_SIZE.0 * PLAYER_SCALE * 2.0,
+        };
+        let mut player = GameObject::new(
+            x,
+            250.0,
+            None,
+            PLAYER_FRAME_SIZE.0 * PLAYER_SCALE,
+            PLAYER_FRAME_SIZE.1 * PLAYER_SCALE,
+            Some(Box::new(player_physics_system)),
+            true,
+            None,
+            GameObjectType::Player,
+            Some(Box::new(PlayerLifeSystem::new(
+                self.config.game.lives,
+                &self.config.player,
+            ))),
+        );
+        player.player_number = Some(player_number);
+        player
+    }
+
+    /// Starts on the far side of the arena from the streamer
+    fn create_boss(&mut self) -> Option<GameObject> {
+        let boss = &self.config.boss;
+        let object = self.catalog.get(&boss.object)?;
+        let size = (
+            object.sprite.frame_width * boss.scale,
+            object.sprite.frame_height * boss.scale,
+        );
+        let (send_boss_action, boss_actions) = channel();
+        self.send_boss_action = Some(send_boss_action);
+        let mut game_object = GameObject::new(
+            self.arena_size.0 - size.0,
+            self.arena_size.1 - size.1,
+            None,
+            size.0,
+            size.1,
+            Some(Box::new(BossPhysics::new(
+                boss_actions,
+                boss.speed,
+                boss.jump_force,
+            ))),
+            true,
+            None,
+            GameObjectType::Boss,
+            Some(Box::new(BossLifeSystem::new(
+                boss.health,
+                self.config
+                    .game
+                    .seconds_to_ticks(boss.iframes_seconds)
+                    .min(u32::MAX.into()) as u32,
+            ))),
+        );
+        game_object.object_name = Some(object.name.clone());
+        Some(game_object)
+    }
+
+    fn send_game_started_message(&self) {
+        let message = if self.config.endless.enabled {
+            format!(
+                "It's endless mode, Get the Streamer before they survive too long! A new wave starts every {} seconds",
+                self.config.endless.wave_seconds
+            )
+        } else {
+            format!(
+                "You have {} seconds to send your commands to Get the Streamer!",
+                self.config.game.game_time_seconds
+            )
+        };
+        if let Err(error) = self.send_to_chat.send(message) {
+            eprintln!("error sending game started message to chat: {}", error);
+        }
+        if self.config.boss.enabled {
+            self.send_message(
+                "It's boss mode! Steer the boss with #left, #right, #slam and #spit".to_owned(),
+            );
+        }
+    }
+
+    fn send_game_ended_message(&self, winner: RunningState) {
+        let (highest_scorer, score) = self
+            .get_highest_scorer()
+            .unwrap_or_else(|| ("nobody".to_owned(), 0));
+
+        let survival_seconds = self.survival_time().as_secs();
+        let message = match winner {
+            _ if self.config.endless.enabled => format!(
+                "The streamer survived {}:{:02} and made it to wave {}, highest scorer was {} with {} points!",
+                survival_seconds / 60,
+                survival_seconds % 60,
+                self.wave,
+                highest_scorer,
+                score
+            ),
+            RunningState::ChatWon => format!(
+                "You all won, highest scorer was {} with {} points!",
+                highest_scorer, score
+            ),
+            _ => format!(
+                "The Streamer won the game despite the best efforts of {} who got {} points!",
+                highest_scorer, score
+            ),
+        };
+
+        let message = match (self.winning_team(), self.team_scores().as_slice()) {
+            (Some(team), [(_winner, winning_score), (_loser, losing_score)]) => format!(
+                "{} The {} team wins with {} points to {}!",
+                message,
+                team.name(),
+                winning_score,
+                losing_score
+            ),
+            (None, [(_team, score), _]) => {
+                format!("{} The teams tied with {} points each!", message, score)
+            }
+            _ => message,
+        };
+
+        if let Err(error) = self.send_to_chat.send(message) {
+            eprintln!("error sending game ended message to chat: {}", error);
+        }
+    }
+
+    fn end_game(&mut self, new_running_state: RunningState) {
+        // the last hits of the round still count
+        self.dispatch_events();
+        self.running_state = new_running_state;
+        self.ticks_left_in_state = self
+            .config
+            .game
+            .duration_to_ticks(self.config.game.intermission());
+        for (name, score) in &self.scores {
+            *self.session_scores.entry(name.to_owned()).or_insert(0) += *score;
+        }
+        for stats in self.round_stats.values_mut() {
+            stats.rounds_played = 1;
+            stats.rounds_won_by_chat = (new_running_state == RunningState::ChatWon) as u64;
+        }
+        self.events.publish(GameEvent::RoundEnded {
+            round: self.round,
+            winner: new_running_state,
+        });
+        self.dispatch_events();
+    }
+}
@@ -0,0 +1,25 @@
+use crate::game_event::GameEvent;
+
+/// Sounds the game plays for what happens in the simulation, which has no audio device of its own
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SoundEffect {
+    ObjectDropped,
+    Jump,
+    Heart,
+    Hit,
+}
+
+impl SoundEffect {
+    /// The sound that goes with a game event, most events are silent
+    pub fn for_event(event: &GameEvent) -> Option<SoundEffect> {
+        match event {
+            GameEvent::ObjectSpawned { .. } => Some(SoundEffect::ObjectDropped),
+            GameEvent::PlayerJumped { .. } => Some(SoundEffect::Jump),
+            GameEvent::PlayerHealed { .. } => Some(SoundEffect::Heart),
+            GameEvent::PlayerHit { .. }
+            | GameEvent::PlayerHurtByHeart { .. }
+            | GameEvent::BossHurt { .. } => Some(SoundEffect::Hit),
+            _ => None,
+        }
+    }
+}
@@ -18,7 +18,7 @@ pub struct Sprite {
 impl Sprite {
     pub fn new(
         context: &mut Context,
-        path: &'static str,
+        path: &str,
         sprites_accross: u16,
         sprites_down: u16,
     ) -> GameResult<Sprite> {
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use ggez::graphics::Color;
+
+/// The two sides chat is split into in team mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Team {
+    Red,
+    Blue,
+}
+
+impl Team {
+    pub const ALL: [Team; 2] = [Team::Red, Team::Blue];
+
+    pub fn parse(name: &str) -> Option<Team> {
+        match name.to_lowercase().as_str() {
+            "red" => Some(Team::Red),
+            "blue" => Some(Team::Blue),
+            _ => None,
+        }
+    }
+
+    /// The team asked for in `!join <team>`, empty when none was given and `None` when the
+    /// message isn't a join
+    pub fn parse_join(message: &str) -> Option<&str> {
+        let mut words = message.split_whitespace();
+        if words.next()? != "!join" {
+            return None;
+        }
+        Some(words.next().unwrap_or_default())
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Team::Red => "red",
+            Team::Blue => "blue",
+        }
+    }
+
+    /// For the credits
+    pub fn title(self) -> &'static str {
+        match self {
+            Team::Red => "Red",
+            Team::Blue => "Blue",
+        }
+    }
+
+    /// The label color for everything the team drops
+    pub fn color(self) -> Color {
+        match self {
+            Team::Red => Color::from_rgb(235, 64, 52),
+            Team::Blue => Color::from_rgb(66, 135, 245),
+        }
+    }
+}
+
+/// Who is on which team, kept for the whole session so teams carry over between rounds.
+/// Chatters pick with `!join red` or `!join blue`, whoever drops something without picking
+/// goes on the smaller team.
+#[derive(Debug, Default)]
+pub struct Teams {
+    members: HashMap<String, Team>,
+}
+
+impl Teams {
+    pub fn get(&self, name: &str) -> Option<Team> {
+        self.members.get(&name.to_lowercase()).copied()
+    }
+
+    /// Errors with the team the chatter is already on, teams can't be switched
+    pub fn join(&mut self, name: &str, team: Team) -> Result<(), Team> {
+        match self.get(name) {
+            Some(current_team) => Err(current_team),
+            None => {
+                self.members.insert(name.to_lowercase(), team);
+                Ok(())
+            }
+        }
+    }
+
+    /// The chatter's team, putting them on the smaller one if they don't have one yet
+    pub fn get_or_assign(&mut self, name: &str) -> Team {
+        if let Some(team) = self.get(name) {
+            return team;
+        }
+        let team = Team::ALL
+            .iter()
+            .copied()
+            .min_by_key(|team| self.size(*team))
+            .unwrap_or(Team::Red);
+        self.members.insert(name.to_lowercase(), team);
+        team
+    }
+
+    pub fn size(&self, team: Team) -> usize {
+        self.members
+            .values()
+            .filter(|member_team| **member_team == team)
+            .count()
+    }
+}
@@ -1,43 +1,17 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-use std::path::Path;
+use std::fs::{self, File};
+use std::io::{prelude::*, BufReader, ErrorKind};
+use std::path::{Path, PathBuf};
 
-use ggez::{filesystem, Context};
+use eyre::Result;
 
 pub fn load_messages(file_name: impl AsRef<Path>) -> Option<impl Iterator<Item = String>> {
     Some(
         BufReader::new(get_resource(file_name)?)
             .lines()
-            .filter_map(|message| message.ok()),
+            .map_while(Result::ok),
     )
 }
 
-pub fn load_scores(file_name: &str, context: &mut Context) -> HashMap<String, u128> {
-    if filesystem::exists(context, file_name) {
-        match filesystem::open(context, file_name) {
-            Ok(high_scores_file) => deserialize_high_scores(high_scores_file),
-            Err(_) => HashMap::new(),
-        }
-    } else {
-        HashMap::new()
-    }
-}
-
-pub fn save_scores(
-    context: &mut Context,
-    file_name: &str,
-    scores: &HashMap<String, u128>,
-) -> eyre::Result<()> {
-    let mut scores_file = filesystem::create(context, file_name)?;
-    let mut serialized_scores = String::new();
-    serialize_hashmap(scores, &mut serialized_scores);
-    if let Err(error) = scores_file.write(serialized_scores.as_bytes()) {
-        eprintln!("{}", error);
-    };
-    Ok(())
-}
-
 /// opens a file from the resources folder
 /// takes an `AsRef<Path>` for convenience. with this, a `&str` can be used
 fn get_resource(path: impl AsRef<Path>) -> Option<File> {
@@ -47,24 +21,62 @@ fn get_resource(path: impl AsRef<Path>) -> Option<File> {
     File::open(path_buf).ok()
 }
 
-fn serialize_hashmap(hashmap: &HashMap<String, u128>, output: &mut String) {
-    for (username, points) in hashmap {
-        let points = format!("{}\r\n", points);
-        output.push_str(username);
-        output.push(':');
-        output.push_str(&points);
+/// The previous version of a file written with `write_atomically`
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup_path = path.as_os_str().to_owned();
+    backup_path.push(".bak");
+    PathBuf::from(backup_path)
+}
+
+/// Writes a temp file and renames it over `path`, so a crash mid-save can't leave half a file.
+/// The old file is kept at `backup_path(path)`.
+pub fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(contents.as_bytes())?;
+    temp_file.sync_all()?;
+
+    if path.exists() {
+        fs::copy(path, backup_path(path))?;
     }
+    fs::rename(&temp_path, path)?;
+    Ok(())
 }
 
-fn deserialize_high_scores(file: filesystem::File) -> HashMap<String, u128> {
-    let mut scores = HashMap::new();
-    for high_score_line in BufReader::new(file).lines() {
-        if let Ok(raw_high_score) = high_score_line {
-            let high_score: Vec<&str> = raw_high_score.trim().split(':').collect();
-            let score: u128 = high_score[1].parse().unwrap_or_else(|_| 0);
-            let username = high_score[0].to_owned();
-            scores.insert(username, score);
+/// Parses `path`, falling back to the backup when it's damaged. Everything that went wrong is
+/// added to `problems`. `None` when there is no file yet or nothing could be read.
+pub fn read_with_backup<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T>,
+    problems: &mut Vec<String>,
+) -> Option<T> {
+    match fs::read_to_string(path) {
+        Ok(raw) => match parse(&raw) {
+            Ok(parsed) => return Some(parsed),
+            Err(error) => problems.push(format!("{:?}: {}", path, error)),
+        },
+        Err(error) if error.kind() == ErrorKind::NotFound => return None,
+        Err(error) => problems.push(format!("{:?}: {}", path, error)),
+    }
+
+    let backup_path = backup_path(path);
+    match fs::read_to_string(&backup_path)
+        .map_err(eyre::Report::from)
+        .and_then(|raw| parse(&raw))
+    {
+        Ok(parsed) => {
+            problems.push(format!("using the backup from {:?}", backup_path));
+            Some(parsed)
+        }
+        Err(error) => {
+            problems.push(format!("{:?}: {}", backup_path, error));
+            None
         }
     }
-    scores
 }
@@ -0,0 +1,143 @@
+use std::cmp::Reverse;
+
+use crate::chatter::Chatter;
+use crate::object_catalog::ObjectCatalog;
+
+/// One chatter's pick for the next drop, `#vote sword 4`. The column can be left out
+/// to only vote on the object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vote {
+    /// The name of the object in the object catalog
+    pub object: String,
+    pub column: Option<u8>,
+}
+
+impl Vote {
+    /// `None` when the message isn't a vote at all, the error is the reply for chat
+    pub fn parse(
+        message: &str,
+        catalog: &ObjectCatalog,
+        drop_zone_count: u8,
+    ) -> Option<Result<Vote, &'static str>> {
+        let mut words = message.split_whitespace();
+        if words.next()? != "#vote" {
+            return None;
+        }
+
+        let object = match words
+            .next()
+            .and_then(|alias| catalog.find_by_alias(alias.trim_start_matches('#')))
+        {
+            Some(object) => object.name.clone(),
+            None => return Some(Err("Vote for something to drop, like #vote sword 4")),
+        };
+        let column = match words.next().map(str::parse::<u8>) {
+            None => None,
+            Some(Ok(column)) if column < drop_zone_count => Some(column),
+            Some(Ok(_)) => return Some(Err("The given column is outside of the arena")),
+            Some(Err(_)) => return Some(Err("I couldn't tell what column to vote for")),
+        };
+        Some(Ok(Vote { object, column }))
+    }
+}
+
+/// What chat decided when a vote closed
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoteResult {
+    pub object: String,
+    /// `None` when nobody voted for a column
+    pub column: Option<u8>,
+    /// How many chatters voted, for anything
+    pub voters: usize,
+    /// The first chatter to vote for the winning object, the drop is theirs
+    pub leader: Chatter,
+}
+
+/// The votes for the next drop. A window opens with the first vote and everyone gets one vote
+/// in it, voting again changes it. Ties go to whatever was voted for first, so replays agree.
+#[derive(Debug, Default)]
+pub struct VoteTally {
+    /// In the order chatters first voted
+    votes: Vec<(Chatter, Vote)>,
+    closes_at: Option<u64>,
+}
+
+impl VoteTally {
+    pub fn cast(&mut self, chatter: Chatter, vote: Vote, tick: u64, window_ticks: u64) {
+        self.closes_at
+            .get_or_insert(tick.saturating_add(window_ticks));
+        match self
+            .votes
+            .iter_mut()
+            .find(|(voter, _vote)| voter.name == chatter.name)
+        {
+            Some((_voter, existing_vote)) => *existing_vote = vote,
+            None => self.votes.push((chatter, vote)),
+        }
+    }
+
+    /// Ends the vote once its window is over, leaving the tally empty for the next one
+    pub fn close(&mut self, tick: u64) -> Option<VoteResult> {
+        if self.closes_at.is_none_or(|closes_at| tick < closes_at) {
+            return None;
+        }
+        let (object, _votes) = self.object_counts().into_iter().next()?;
+        let column = self
+            .column_counts()
+            .into_iter()
+            .next()
+            .map(|(column, _votes)| column);
+        let leader = self
+            .votes
+            .iter()
+            .find(|(_voter, vote)| vote.object == object)
+            .map(|(voter, _vote)| voter.clone())?;
+        let voters = self.votes.len();
+        self.clear();
+
+        Some(VoteResult {
+            object,
+            column,
+            voters,
+            leader,
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.votes.clear();
+        self.closes_at = None;
+    }
+
+    /// Votes for each object, most first
+    pub fn object_counts(&self) -> Vec<(String, usize)> {
+        count_in_order(self.votes.iter().map(|(_voter, vote)| vote.object.clone()))
+    }
+
+    /// Votes for each column, most first
+    pub fn column_counts(&self) -> Vec<(u8, usize)> {
+        count_in_order(self.votes.iter().filter_map(|(_voter, vote)| vote.column))
+    }
+
+    /// Ticks until the vote closes, `None` before anyone has voted
+    pub fn ticks_left(&self, tick: u64) -> Option<u64> {
+        self.closes_at
+            .map(|closes_at| closes_at.saturating_sub(tick))
+    }
+}
+
+/// Counts each value, sorted by count with ties in the order they were first seen
+pub(crate) fn count_in_order<T: PartialEq>(values: impl Iterator<Item = T>) -> Vec<(T, usize)> {
+    let mut counts: Vec<(T, usize)> = vec![];
+    for value in values {
+        match counts
+            .iter_mut()
+            .find(|(counted, _count)| *counted == value)
+        {
+            Some((_counted, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    // stable, so ties keep their order
+    counts.sort_by_key(|(_value, count)| Reverse(*count));
+    counts
+}
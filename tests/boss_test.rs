@@ -0,0 +1,132 @@
+use std::sync::mpsc::{channel, Receiver};
+
+use get_the_streamer_game::boss::{BossAction, BossTally};
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::input_state::{InputState, PlayerNumber};
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::replay::ReplayChatMessage;
+use get_the_streamer_game::running_state::RunningState;
+use get_the_streamer_game::Simulation;
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+
+fn create_boss_simulation() -> (Simulation, Receiver<String>) {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.boss.enabled = true;
+    config.boss.health = 2;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        1337,
+        config,
+        ObjectCatalog::default(),
+    );
+    while simulation.running_state() == RunningState::StartingSoon {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+    (simulation, receive_from_game)
+}
+
+fn boss_x(simulation: &Simulation) -> f32 {
+    simulation
+        .game_objects()
+        .iter()
+        .find(|game_object| game_object.object_name.as_deref() == Some("snake"))
+        .unwrap()
+        .location
+        .x
+}
+
+fn chat(simulation: &mut Simulation, name: &str, message: &str) {
+    let chat_message = ReplayChatMessage {
+        tick: simulation.ticks(),
+        name: name.to_owned(),
+        color: (0, 0, 0),
+        subscriber: false,
+        badges: vec![],
+        bits: 0,
+        custom_reward_id: None,
+        message: message.to_owned(),
+    };
+    let commands = simulation.create_commands(&[chat_message]);
+    assert!(commands.is_empty());
+}
+
+fn tick_for_seconds(simulation: &mut Simulation, seconds: f32) {
+    let ticks = simulation.config().game.seconds_to_ticks(seconds);
+    for _ in 0..ticks {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+}
+
+#[test]
+fn the_majority_decides_what_the_boss_does() {
+    let chatter = |name: &str| Chatter::new(name.to_owned(), (0, 0, 0), false);
+    let mut tally = BossTally::default();
+
+    assert_eq!(BossAction::parse("#slam now"), Some(BossAction::Slam));
+    assert_eq!(BossAction::parse("#sword 3"), None);
+    tally.cast(chatter("a"), BossAction::Left, 0, 10);
+    tally.cast(chatter("b"), BossAction::Spit, 5, 10);
+    tally.cast(chatter("c"), BossAction::Spit, 5, 10);
+    assert_eq!(tally.decide(9), None);
+
+    let (action, leader) = tally.decide(10).unwrap();
+    assert_eq!(action, BossAction::Spit);
+    assert_eq!(leader.name, "b");
+    assert_eq!(tally.decide(20), None);
+}
+
+#[test]
+fn chat_steers_the_boss() {
+    let (mut simulation, _receive_from_game) = create_boss_simulation();
+    let window_seconds = simulation.config().boss.window_seconds;
+    let starting_x = boss_x(&simulation);
+
+    chat(&mut simulation, "test_bot1", "#left");
+    chat(&mut simulation, "test_bot2", "#sword 3");
+    tick_for_seconds(&mut simulation, window_seconds + 1.0);
+    assert!(boss_x(&simulation) < starting_x);
+    // plain drops are ignored in boss mode
+    assert_eq!(simulation.game_objects().len(), 2);
+
+    chat(&mut simulation, "test_bot1", "#spit");
+    tick_for_seconds(&mut simulation, window_seconds + 0.1);
+    let spit = simulation
+        .game_objects()
+        .iter()
+        .find(|game_object| game_object.object_name.as_deref() == Some("fire"))
+        .unwrap();
+    assert_eq!(spit.chatter.as_ref().unwrap().name, "test_bot1");
+}
+
+#[test]
+fn the_streamer_wins_by_landing_on_the_boss() {
+    let (mut simulation, receive_from_game) = create_boss_simulation();
+    assert_eq!(simulation.boss_health(), Some(2));
+
+    // drop the streamer onto the boss, bouncing off it lands on it again
+    let boss_location = simulation
+        .game_objects()
+        .iter()
+        .find(|game_object| game_object.object_name.is_some())
+        .unwrap()
+        .location;
+    let player = simulation
+        .game_objects_mut()
+        .iter_mut()
+        .find(|game_object| game_object.chatter.is_none() && game_object.object_name.is_none())
+        .unwrap();
+    player.location.x = boss_location.x + boss_location.w / 2.0;
+    player.location.y = boss_location.y - player.location.h - 50.0;
+
+    tick_for_seconds(&mut simulation, 5.0);
+
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+    assert_eq!(simulation.player_lives_left(PlayerNumber::One), 3);
+    let chat: Vec<String> = receive_from_game.try_iter().collect();
+    assert!(chat.contains(&"The streamer landed on the boss, 1 health left!".to_owned()));
+    assert!(chat.contains(&"The streamer beat the boss!".to_owned()));
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+
+use get_the_streamer_game::chat_query::{ChatQueries, ChatQuery};
+use get_the_streamer_game::chat_transport::IncomingChat;
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::command::Command;
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::input_state::InputState;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::Simulation;
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+
+/// The query answers go to the same chat as the simulation's messages
+fn create_simulation() -> (Simulation, ChatQueries, Receiver<String>) {
+    let (send_to_chat, receive_from_game) = channel();
+    let chat_queries = ChatQueries::new(send_to_chat.clone());
+    let simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        1337,
+        Config::default(),
+        ObjectCatalog::default(),
+    );
+    (simulation, chat_queries, receive_from_game)
+}
+
+fn play_until_dropped(simulation: &mut Simulation, message: &str, name: &str) {
+    let game = Config::default().game;
+    for _ in 0..game.duration_to_ticks(game.splash_duration()) {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+    let command = Command::new(
+        message,
+        Chatter::new(name.to_owned(), (0, 0, 0), false),
+        &Config::default(),
+        &ObjectCatalog::default(),
+        simulation.rng(),
+    )
+    .unwrap()
+    .unwrap();
+    simulation.tick(&InputState::default(), vec![command]);
+}
+
+fn ask(
+    chat_queries: &mut ChatQueries,
+    simulation: &Simulation,
+    high_scores: &HashMap<String, u128>,
+    receive_from_game: &Receiver<String>,
+    name: &str,
+    message: &str,
+) -> Option<String> {
+    receive_from_game.try_iter().for_each(drop);
+    assert!(chat_queries.handle(&IncomingChat::new(name, message), simulation, high_scores));
+    receive_from_game.try_iter().last()
+}
+
+#[test]
+fn only_queries_are_parsed() {
+    assert_eq!(ChatQuery::parse("!score"), Some(ChatQuery::Score));
+    assert_eq!(ChatQuery::parse("!top please"), Some(ChatQuery::Top));
+    assert_eq!(ChatQuery::parse("#sword 3"), None);
+    assert_eq!(ChatQuery::parse("what is !score"), None);
+}
+
+#[test]
+fn score_counts_this_round_on_top_of_the_saved_scores() {
+    let (mut simulation, mut chat_queries, receive_from_game) = create_simulation();
+    play_until_dropped(&mut simulation, "#sword 3", "test_bot1");
+    let mut high_scores = HashMap::new();
+    high_scores.insert("test_bot1".to_owned(), 40);
+
+    let answer = ask(
+        &mut chat_queries,
+        &simulation,
+        &high_scores,
+        &receive_from_game,
+        "test_bot1",
+        "!score",
+    );
+
+    assert_eq!(
+        answer.as_deref(),
+        Some("@test_bot1 you have 1 points this round and 41 all-time")
+    );
+}
+
+#[test]
+fn chatters_have_to_wait_out_the_cooldown() {
+    let (mut simulation, mut chat_queries, receive_from_game) = create_simulation();
+    play_until_dropped(&mut simulation, "#sword 3", "test_bot1");
+    let high_scores = HashMap::new();
+
+    let first = ask(
+        &mut chat_queries,
+        &simulation,
+        &high_scores,
+        &receive_from_game,
+        "test_bot1",
+        "!round",
+    );
+    let second = ask(
+        &mut chat_queries,
+        &simulation,
+        &high_scores,
+        &receive_from_game,
+        "test_bot1",
+        "!help",
+    );
+    let someone_else = ask(
+        &mut chat_queries,
+        &simulation,
+        &high_scores,
+        &receive_from_game,
+        "test_bot2",
+        "!help",
+    );
+
+    assert_eq!(first.as_deref(), Some("Round 1: 1. test_bot1 (1)"));
+    assert_eq!(second, None);
+    assert!(someone_else
+        .unwrap()
+        .contains("fire, sword, snake, heart, random"));
+
+    let config = Config::default();
+    for _ in 0..config.game.duration_to_ticks(config.chat.query_cooldown()) {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+    let after_the_cooldown = ask(
+        &mut chat_queries,
+        &simulation,
+        &high_scores,
+        &receive_from_game,
+        "test_bot1",
+        "!round",
+    );
+    assert_eq!(
+        after_the_cooldown.as_deref(),
+        Some("Round 1: 1. test_bot1 (1)")
+    );
+}
+
+#[test]
+fn chatters_with_a_display_name_are_looked_up_by_it() {
+    let (mut simulation, mut chat_queries, receive_from_game) = create_simulation();
+    play_until_dropped(&mut simulation, "#sword 3", "XiTheDwarf");
+    let mut high_scores = HashMap::new();
+    high_scores.insert("XiTheDwarf".to_owned(), 40);
+    let chat = IncomingChat {
+        display_name: Some("XiTheDwarf".to_owned()),
+        ..IncomingChat::new("xithedwarf", "!score")
+    };
+
+    assert!(chat_queries.handle(&chat, &simulation, &high_scores));
+    assert_eq!(
+        receive_from_game.try_iter().last().as_deref(),
+        Some("@XiTheDwarf you have 1 points this round and 41 all-time")
+    );
+    // the cooldown is kept under the same name
+    assert!(chat_queries.handle(&chat, &simulation, &high_scores));
+    assert_eq!(receive_from_game.try_iter().last(), None);
+}
@@ -0,0 +1,79 @@
+use std::sync::mpsc::channel;
+use std::time::{Duration, Instant};
+
+use get_the_streamer_game::chat_queue::ChatQueue;
+use get_the_streamer_game::chat_transport::IncomingChat;
+use get_the_streamer_game::config::ChatConfig;
+
+fn messages(chat: &[IncomingChat]) -> Vec<&str> {
+    chat.iter().map(|chat| chat.message.as_str()).collect()
+}
+
+#[test]
+fn a_few_messages_are_handled_each_frame_in_order() {
+    let (send_to_game, receive_from_chat) = channel();
+    let mut chat_queue = ChatQueue::new(&ChatConfig {
+        messages_per_frame: 2,
+        ..ChatConfig::default()
+    });
+    for index in 0..5 {
+        send_to_game
+            .send(IncomingChat::new("test_bot1", &format!("#sword {}", index)))
+            .unwrap();
+    }
+    let now = Instant::now();
+
+    chat_queue.receive(&receive_from_chat, now);
+
+    assert_eq!(chat_queue.len(), 5);
+    assert_eq!(messages(&chat_queue.take(now)), ["#sword 0", "#sword 1"]);
+    assert_eq!(messages(&chat_queue.take(now)), ["#sword 2", "#sword 3"]);
+    assert_eq!(messages(&chat_queue.take(now)), ["#sword 4"]);
+    assert!(chat_queue.is_empty());
+}
+
+#[test]
+fn old_messages_are_thrown_away() {
+    let (send_to_game, receive_from_chat) = channel();
+    let mut chat_queue = ChatQueue::new(&ChatConfig {
+        max_message_age_seconds: 5.0,
+        ..ChatConfig::default()
+    });
+    let received_at = Instant::now();
+    send_to_game
+        .send(IncomingChat::new("test_bot1", "#fire 1"))
+        .unwrap();
+    chat_queue.receive(&receive_from_chat, received_at);
+    send_to_game
+        .send(IncomingChat::new("test_bot2", "#fire 2"))
+        .unwrap();
+    chat_queue.receive(&receive_from_chat, received_at + Duration::from_secs(4));
+
+    let taken = chat_queue.take(received_at + Duration::from_secs(6));
+
+    assert_eq!(messages(&taken), ["#fire 2"]);
+    assert_eq!(chat_queue.dropped_stale(), 1);
+}
+
+#[test]
+fn the_oldest_messages_go_when_the_queue_is_full() {
+    let (send_to_game, receive_from_chat) = channel();
+    let mut chat_queue = ChatQueue::new(&ChatConfig {
+        max_queued_messages: 3,
+        ..ChatConfig::default()
+    });
+    for index in 0..5 {
+        send_to_game
+            .send(IncomingChat::new("test_bot1", &format!("#snake {}", index)))
+            .unwrap();
+    }
+    let now = Instant::now();
+
+    chat_queue.receive(&receive_from_chat, now);
+
+    assert_eq!(chat_queue.dropped_full(), 2);
+    assert_eq!(
+        messages(&chat_queue.take(now)),
+        ["#snake 2", "#snake 3", "#snake 4"]
+    );
+}
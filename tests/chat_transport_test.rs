@@ -0,0 +1,87 @@
+use get_the_streamer_game::chat_transport::{
+    ChatTransport, IncomingChat, IrcChatTransport, LocalIrcServer, MockChatTransport,
+};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connects the irc transport to a local server, returning once it has joined the channel
+fn connect_to_local_server() -> (LocalIrcServer, Receiver<IncomingChat>, Sender<String>) {
+    let server = LocalIrcServer::start("127.0.0.1:0").unwrap();
+    let chat_transport = Box::new(
+        IrcChatTransport::anonymous(&server.address().to_string(), vec!["#local".to_owned()])
+            .unwrap(),
+    );
+    let (send_to_game, receive_from_chat) = channel();
+    let (send_to_chat, receive_from_game) = channel();
+    thread::spawn(move || chat_transport.run(send_to_game, receive_from_game));
+
+    let started_at = Instant::now();
+    while server.joined_clients() == 0 {
+        assert!(started_at.elapsed() < TIMEOUT, "never joined the channel");
+        thread::sleep(Duration::from_millis(10));
+    }
+    (server, receive_from_chat, send_to_chat)
+}
+
+#[test]
+fn irc_transport_reads_twitch_tags() {
+    let (server, receive_from_chat, _send_to_chat) = connect_to_local_server();
+    let chat = IncomingChat {
+        name: "xithedwarf".to_owned(),
+        display_name: Some("XiTheDwarf".to_owned()),
+        color: (255, 0, 128),
+        subscriber: true,
+        badges: vec!["moderator".to_owned()],
+        bits: 100,
+        custom_reward_id: Some("5f1a3c2e-giant-sword".to_owned()),
+        message: "#fire 3".to_owned(),
+    };
+
+    server.send_chat(&chat).unwrap();
+
+    let received = receive_from_chat.recv_timeout(TIMEOUT).unwrap();
+    assert_eq!(
+        received,
+        IncomingChat {
+            badges: vec!["moderator".to_owned(), "subscriber".to_owned()],
+            ..chat
+        }
+    );
+    assert_eq!(received.chatter_name(), "XiTheDwarf");
+    assert!(received.has_badge("moderator"));
+}
+
+#[test]
+fn irc_transport_sends_game_messages_to_chat() {
+    let (server, _receive_from_chat, send_to_chat) = connect_to_local_server();
+
+    send_to_chat
+        .send("Hit! xithedwarf gets 10 points".to_owned())
+        .unwrap();
+
+    assert_eq!(
+        server.receive_from_game(TIMEOUT).as_deref(),
+        Some("Hit! xithedwarf gets 10 points")
+    );
+}
+
+#[test]
+fn mock_transport_sends_drop_commands() {
+    let chat_transport = Box::new(MockChatTransport {
+        num_bot_users: 3,
+        initial_commands_delay: Duration::from_millis(0),
+        min_command_interval_ms: 1,
+        max_command_interval_ms: 2,
+        cheer_chance: 0.0,
+    });
+    let (send_to_game, receive_from_chat) = channel();
+    let (_send_to_chat, receive_from_game) = channel();
+    thread::spawn(move || chat_transport.run(send_to_game, receive_from_game));
+
+    let chat = receive_from_chat.recv_timeout(TIMEOUT).unwrap();
+    assert!(chat.name.starts_with("test_bot"));
+    assert!(chat.message.starts_with('#'));
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::fs;
+
+use get_the_streamer_game::chatter_stats::{ChatterStats, ChatterStatsStore};
+
+fn round(drops: &[(&str, u64)], hits_landed: u64, won_by_chat: bool) -> ChatterStats {
+    ChatterStats {
+        drops: drops
+            .iter()
+            .map(|(name, count)| (name.to_string(), *count))
+            .collect(),
+        hits_landed,
+        rounds_played: 1,
+        rounds_won_by_chat: won_by_chat as u64,
+        ..ChatterStats::default()
+    }
+}
+
+#[test]
+fn rounds_add_up_and_remember_when_a_chatter_was_seen() {
+    let mut stats = ChatterStats::default();
+
+    stats.add_round(&round(&[("fire", 2), ("sword", 1)], 1, true), 1000);
+    stats.add_round(&round(&[("sword", 3)], 0, false), 2000);
+
+    assert_eq!(stats.drops.get("sword"), Some(&4));
+    assert_eq!(stats.total_drops(), 6);
+    assert_eq!(stats.favorite_drop(), Some("sword"));
+    assert_eq!(stats.hits_landed, 1);
+    assert_eq!(stats.rounds_played, 2);
+    assert_eq!(stats.rounds_won_by_chat, 1);
+    assert_eq!((stats.first_seen, stats.last_seen), (1000, 2000));
+}
+
+#[test]
+fn stats_survive_a_save_and_load() {
+    let directory = std::env::temp_dir().join(format!("gts_chatter_stats_{}", std::process::id()));
+    fs::remove_dir_all(&directory).ok();
+    let store = ChatterStatsStore::new(&directory);
+    let mut chatters = HashMap::new();
+    chatters.insert("xithedwarf".to_owned(), round(&[("snake", 4)], 2, true));
+
+    store.save(&chatters).unwrap();
+    let loaded = store.load();
+
+    assert_eq!(loaded.chatters, chatters);
+    assert!(loaded.problems.is_empty(), "{:?}", loaded.problems);
+}
@@ -0,0 +1,190 @@
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+
+#[test]
+fn missing_keys_keep_their_defaults() {
+    let config = Config::from_toml(
+        r#"
+        version = 2
+
+        [game]
+        lives = 5
+
+        [player]
+        jump_force = -900.0
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.game.lives, 5);
+    assert_eq!(config.player.jump_force, -900.0);
+    assert_eq!(config.game.game_time_seconds, 120);
+    assert_eq!(config.physics, Config::default().physics);
+}
+
+#[test]
+fn empty_file_is_the_default_config() {
+    assert_eq!(Config::from_toml("").unwrap(), Config::default());
+}
+
+#[test]
+fn per_tick_configs_are_converted_to_per_second() {
+    let config = Config::from_toml(
+        r#"
+        [game]
+        framerate_target = 30
+
+        [physics]
+        gravity_force = 1
+
+        [player]
+        jump_force = -12.5
+        friction = 0.15
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.physics.gravity_force, 900.0);
+    assert_eq!(config.player.jump_force, -375.0);
+    assert!((config.player.friction - 4.875).abs() < 0.01);
+    // left out, so it's the per second default rather than converted
+    assert_eq!(
+        config.physics.snake_speed,
+        Config::default().physics.snake_speed
+    );
+
+    let error = Config::from_toml(
+        "version = 3
+",
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("newer version"), "{}", error);
+}
+
+#[test]
+fn unknown_keys_are_named_in_the_error() {
+    let error = Config::from_toml("[game]\nlifes = 5\n").unwrap_err();
+
+    assert!(error.to_string().contains("lifes"), "{}", error);
+}
+
+#[test]
+fn wrong_types_are_named_in_the_error() {
+    let error = Config::from_toml("[physics]\ngravity_force = \"heavy\"\n").unwrap_err();
+
+    assert!(error.to_string().contains("gravity_force"), "{}", error);
+}
+
+#[test]
+fn invalid_values_are_named_in_the_error() {
+    let error = Config::from_toml("[player]\nfriction = 1.5\n").unwrap_err();
+    assert!(error.to_string().contains("player.friction"), "{}", error);
+
+    let error = Config::from_toml("[game]\ndrop_zone_count = 0\n").unwrap_err();
+    assert!(
+        error.to_string().contains("game.drop_zone_count"),
+        "{}",
+        error
+    );
+
+    let error = Config::from_toml("[chat]\nmax_message_age_seconds = inf\n").unwrap_err();
+    assert!(
+        error.to_string().contains("chat.max_message_age_seconds"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn numbers_have_to_be_finite() {
+    let broken = [
+        ("limits", "drop_cooldown_seconds = inf"),
+        ("voting", "window_seconds = inf"),
+        ("boss", "window_seconds = nan"),
+        ("player", "jump_force = -inf"),
+        ("physics", "gravity_force = inf"),
+    ];
+    for (section, line) in broken.iter() {
+        let error = Config::from_toml(&format!("[{}]\n{}\n", section, line)).unwrap_err();
+        let key = format!("{}.{}", section, line.split(' ').next().unwrap());
+        assert!(error.to_string().contains(&key), "{}", error);
+    }
+}
+
+#[test]
+fn premium_drops_match_bit_ranges_and_rewards() {
+    let config = Config::from_toml(
+        r#"
+        [[premium.drops]]
+        name = "a giant sword"
+        object = "sword"
+        scale = 3.0
+        min_bits = 100
+        max_bits = 199
+        reward_id = "sword-reward"
+
+        [[premium.drops]]
+        name = "a swarm of snakes"
+        object = "snake"
+        count = 5
+        min_bits = 200
+        "#,
+    )
+    .unwrap();
+    let premium = &config.premium;
+
+    let name = |bits, reward_id| premium.find(bits, reward_id).map(|drop| drop.name.as_str());
+    assert_eq!(name(0, None), None);
+    assert_eq!(name(99, None), None);
+    assert_eq!(name(150, None), Some("a giant sword"));
+    assert_eq!(name(5000, None), Some("a swarm of snakes"));
+    assert_eq!(name(5000, Some("sword-reward")), Some("a giant sword"));
+    assert_eq!(name(0, Some("some-other-reward")), None);
+    assert_eq!(premium.drops[1].scale, 1.0);
+}
+
+#[test]
+fn premium_drops_must_be_reachable_and_exist() {
+    let error =
+        Config::from_toml("[[premium.drops]]\nname = \"x\"\nobject = \"sword\"\n").unwrap_err();
+    assert!(
+        error.to_string().contains("premium.drops[0].min_bits"),
+        "{}",
+        error
+    );
+
+    let config =
+        Config::from_toml("[[premium.drops]]\nname = \"x\"\nobject = \"dragon\"\nmin_bits = 1\n")
+            .unwrap();
+    let error = config
+        .premium
+        .validate_objects(&ObjectCatalog::default())
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("premium.drops[0].object"),
+        "{}",
+        error
+    );
+    Config::default()
+        .premium
+        .validate_objects(&ObjectCatalog::default())
+        .unwrap();
+}
+
+#[test]
+fn endless_waves_ramp_up_the_difficulty() {
+    let config = Config::default();
+
+    assert_eq!(config.for_wave(1), config);
+    let wave_3 = config.for_wave(3);
+    assert!((wave_3.physics.gravity_force - config.physics.gravity_force * 1.1).abs() < 0.001);
+    assert!((wave_3.physics.snake_speed - config.physics.snake_speed * 1.2).abs() < 0.001);
+    assert!(
+        (wave_3.limits.drop_cooldown_seconds - config.limits.drop_cooldown_seconds * 0.81).abs()
+            < 0.001
+    );
+    assert_eq!(
+        wave_3.limits.max_live_enemies,
+        config.limits.max_live_enemies + 10
+    );
+}
@@ -0,0 +1,125 @@
+use std::sync::mpsc::{channel, Receiver};
+
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::control_command::ControlCommand;
+use get_the_streamer_game::input_state::{InputState, PlayerNumber};
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::replay::ReplayChatMessage;
+use get_the_streamer_game::running_state::RunningState;
+use get_the_streamer_game::Simulation;
+
+fn create_playing_simulation() -> (Simulation, Receiver<String>) {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        (1328.0, 1080.0),
+        1337,
+        Config::default(),
+        ObjectCatalog::default(),
+    );
+    let game = Config::default().game;
+    for _ in 0..game.duration_to_ticks(game.splash_duration()) {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+    assert!(simulation.running_state() == RunningState::Playing);
+    (simulation, receive_from_game)
+}
+
+fn chat(simulation: &mut Simulation, name: &str, badges: &[&str], message: &str) {
+    let chat_message = ReplayChatMessage {
+        tick: simulation.ticks(),
+        name: name.to_owned(),
+        color: (0, 0, 0),
+        subscriber: false,
+        badges: badges.iter().map(ToString::to_string).collect(),
+        bits: 0,
+        custom_reward_id: None,
+        message: message.to_owned(),
+    };
+    let commands = simulation.create_commands(&[chat_message]);
+    simulation.tick(&InputState::default(), commands);
+}
+
+#[test]
+fn control_commands_are_parsed() {
+    assert!(ControlCommand::parse("#sword 3").is_none());
+    assert_eq!(
+        ControlCommand::parse("!gts ban @Raider").unwrap().unwrap(),
+        ControlCommand::Ban("raider".to_owned())
+    );
+    assert_eq!(
+        ControlCommand::parse("!gts lives +2").unwrap().unwrap(),
+        ControlCommand::AddLives(2)
+    );
+    assert!(ControlCommand::parse("!gts lives -1").unwrap().is_err());
+    assert!(ControlCommand::parse("!gts dance").unwrap().is_err());
+}
+
+#[test]
+fn only_the_broadcaster_and_moderators_can_control_the_game() {
+    let (mut simulation, _receive_from_game) = create_playing_simulation();
+
+    chat(&mut simulation, "test_bot1", &["subscriber"], "!gts pause");
+    assert!(simulation.running_state() == RunningState::Playing);
+
+    chat(&mut simulation, "a_mod", &["moderator"], "!gts pause");
+    assert!(simulation.running_state() == RunningState::Paused);
+
+    chat(
+        &mut simulation,
+        "the_streamer",
+        &["broadcaster"],
+        "!gts resume",
+    );
+    assert!(simulation.running_state() == RunningState::Playing);
+}
+
+#[test]
+fn nothing_moves_while_paused() {
+    let (mut simulation, _receive_from_game) = create_playing_simulation();
+    chat(&mut simulation, "test_bot1", &[], "#sword 3");
+    chat(&mut simulation, "a_mod", &["moderator"], "!gts pause");
+    let positions: Vec<(f32, f32)> = simulation
+        .game_objects()
+        .iter()
+        .map(|game_object| (game_object.location.x, game_object.location.y))
+        .collect();
+
+    let game = Config::default().game;
+    for _ in 0..game.duration_to_ticks(game.game_time()) * 2 {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+
+    assert!(simulation.running_state() == RunningState::Paused);
+    let paused_positions: Vec<(f32, f32)> = simulation
+        .game_objects()
+        .iter()
+        .map(|game_object| (game_object.location.x, game_object.location.y))
+        .collect();
+    assert_eq!(paused_positions, positions);
+}
+
+#[test]
+fn banned_chatters_cant_drop_anything() {
+    let (mut simulation, _receive_from_game) = create_playing_simulation();
+
+    chat(&mut simulation, "a_mod", &["moderator"], "!gts ban Raider");
+    chat(&mut simulation, "raider", &[], "#sword 3");
+
+    assert_eq!(simulation.game_objects().len(), 1);
+    assert!(simulation.scores().is_empty());
+}
+
+#[test]
+fn mods_can_give_the_streamer_lives_and_end_the_round() {
+    let (mut simulation, _receive_from_game) = create_playing_simulation();
+
+    chat(&mut simulation, "a_mod", &["moderator"], "!gts lives +1");
+    assert_eq!(
+        simulation.player_lives_left(PlayerNumber::One),
+        Config::default().game.lives + 1
+    );
+
+    chat(&mut simulation, "a_mod", &["moderator"], "!gts end");
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+}
@@ -0,0 +1,201 @@
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::command::Command;
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::game_event::{DeathReason, EventBus, GameEvent};
+use get_the_streamer_game::input_state::{InputState, PlayerNumber};
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::running_state::RunningState;
+use get_the_streamer_game::Simulation;
+use std::sync::mpsc::channel;
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+
+fn create_command(simulation: &mut Simulation, message: &str, name: &str) -> Command {
+    Command::new(
+        message,
+        Chatter::new(name.to_owned(), (0, 0, 0), false),
+        &Config::default(),
+        &ObjectCatalog::default(),
+        simulation.rng(),
+    )
+    .unwrap()
+    .unwrap()
+}
+
+fn tick_for(simulation: &mut Simulation, ticks: u32) {
+    for _ in 0..ticks {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+}
+
+#[test]
+fn every_subscriber_gets_every_event_in_order() {
+    let mut event_bus = EventBus::default();
+    let first = event_bus.subscribe();
+    let second = event_bus.subscribe();
+    drop(event_bus.subscribe());
+
+    event_bus.publish(GameEvent::RoundStarted { round: 1 });
+    event_bus
+        .publisher()
+        .send(GameEvent::BossHurt { health_left: 2 })
+        .unwrap();
+    assert!(first.try_recv().is_err());
+
+    let expected = vec![
+        GameEvent::RoundStarted { round: 1 },
+        GameEvent::BossHurt { health_left: 2 },
+    ];
+    assert_eq!(event_bus.dispatch(), expected);
+    assert_eq!(first.try_iter().collect::<Vec<_>>(), expected);
+    assert_eq!(second.try_iter().collect::<Vec<_>>(), expected);
+    assert!(event_bus.dispatch().is_empty());
+}
+
+#[test]
+fn a_round_tells_subscribers_what_happened() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        1337,
+        Config::default(),
+        ObjectCatalog::default(),
+    );
+    let events = simulation.subscribe();
+    let game = Config::default().game;
+    tick_for(
+        &mut simulation,
+        game.duration_to_ticks(game.splash_duration()),
+    );
+    assert_eq!(
+        events.try_iter().collect::<Vec<_>>(),
+        vec![GameEvent::RoundStarted { round: 1 }]
+    );
+
+    // the player is standing under column 2, where the fire will stay burning
+    let fire = create_command(&mut simulation, "#fire 2", "test_bot1");
+    simulation.tick(&InputState::default(), vec![fire]);
+    tick_for(&mut simulation, 6 * game.framerate_target);
+
+    let events: Vec<GameEvent> = events.try_iter().collect();
+    let chatter = Chatter::new("test_bot1".to_owned(), (0, 0, 0), false);
+    assert_eq!(
+        events[0],
+        GameEvent::ObjectSpawned {
+            object_name: "fire".to_owned(),
+            dropped_by: Some(chatter.clone())
+        }
+    );
+    let hits = events
+        .iter()
+        .filter(|event| {
+            **event
+                == GameEvent::PlayerHit {
+                    player_number: PlayerNumber::One,
+                    chatter: Some(chatter.clone()),
+                }
+        })
+        .count();
+    assert_eq!(hits, 3);
+    assert!(events.contains(&GameEvent::ObjectDied {
+        object_name: None,
+        chatter: None,
+        reason: DeathReason::OutOfLives
+    }));
+    assert_eq!(
+        events.last(),
+        Some(&GameEvent::RoundEnded {
+            round: 1,
+            winner: RunningState::ChatWon
+        })
+    );
+}
+
+#[test]
+fn objects_say_why_they_died() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        1337,
+        Config::default(),
+        ObjectCatalog::default(),
+    );
+    let events = simulation.subscribe();
+    let game = Config::default().game;
+    tick_for(
+        &mut simulation,
+        game.duration_to_ticks(game.splash_duration()),
+    );
+
+    let fire = create_command(&mut simulation, "#fire 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![fire]);
+    tick_for(&mut simulation, 90);
+    let sword = create_command(&mut simulation, "#sword 9", "test_bot2");
+    simulation.tick(&InputState::default(), vec![sword]);
+    tick_for(&mut simulation, 6 * game.framerate_target);
+
+    let deaths: Vec<(Option<String>, DeathReason)> = events
+        .try_iter()
+        .filter_map(|event| match event {
+            GameEvent::ObjectDied {
+                object_name,
+                reason,
+                ..
+            } => Some((object_name, reason)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        deaths,
+        vec![
+            (
+                Some("sword".to_owned()),
+                DeathReason::Destroyed {
+                    by: "fire".to_owned()
+                }
+            ),
+            (Some("fire".to_owned()), DeathReason::TimeRanOut),
+        ]
+    );
+}
+
+#[test]
+fn hearts_caught_with_full_lives_hurt() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut config = Config::default();
+    config.player.max_lives = config.game.lives;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        1337,
+        config.clone(),
+        ObjectCatalog::default(),
+    );
+    let events = simulation.subscribe();
+    let game = config.game;
+    tick_for(
+        &mut simulation,
+        game.duration_to_ticks(game.splash_duration()),
+    );
+
+    // the player is standing under column 2
+    let heart = create_command(&mut simulation, "#heart 2", "test_bot1");
+    simulation.tick(&InputState::default(), vec![heart]);
+    tick_for(&mut simulation, 10 * game.framerate_target);
+
+    let chatter = Chatter::new("test_bot1".to_owned(), (0, 0, 0), false);
+    let events: Vec<GameEvent> = events.try_iter().collect();
+    assert!(events.contains(&GameEvent::PlayerHurtByHeart {
+        player_number: PlayerNumber::One,
+        chatter: Some(chatter)
+    }));
+    assert_eq!(
+        simulation.player_lives_left(PlayerNumber::One),
+        game.lives - 1
+    );
+    let stats = &simulation.round_stats()["test_bot1"];
+    assert_eq!(stats.hearts_that_hurt, 1);
+    assert_eq!(stats.hearts_given, 0);
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use get_the_streamer_game::high_scores::{parse_legacy_scores, HighScoreStore};
+
+fn empty_directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("gts_high_scores_{}_{}", name, std::process::id()));
+    fs::remove_dir_all(&directory).ok();
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn scores(scores: &[(&str, u128)]) -> HashMap<String, u128> {
+    scores
+        .iter()
+        .map(|(name, score)| (name.to_string(), *score))
+        .collect()
+}
+
+#[test]
+fn scores_survive_a_save_and_load_even_with_odd_names() {
+    let store = HighScoreStore::new(empty_directory("round_trip"));
+    let saved = scores(&[("xithedwarf", 12), ("colon:name", 7), ("quote\"\nname", 3)]);
+
+    store.save(&saved).unwrap();
+    let loaded = store.load();
+
+    assert_eq!(loaded.scores, saved);
+    assert!(loaded.problems.is_empty(), "{:?}", loaded.problems);
+}
+
+#[test]
+fn legacy_scores_are_migrated_once_and_corrupt_lines_reported() {
+    let directory = empty_directory("legacy");
+    fs::write(
+        directory.join("high_scores"),
+        "xithedwarf:12\r\nnot a score\r\nbrookzerker:lots\r\ntest_bot1:4\r\n",
+    )
+    .unwrap();
+    let store = HighScoreStore::new(&directory);
+
+    let loaded = store.load();
+
+    assert_eq!(
+        loaded.scores,
+        scores(&[("xithedwarf", 12), ("test_bot1", 4)])
+    );
+    assert_eq!(loaded.problems.len(), 2, "{:?}", loaded.problems);
+    assert!(
+        loaded.problems[0].contains("line 2"),
+        "{:?}",
+        loaded.problems
+    );
+    assert!(
+        loaded.problems[1].contains("line 3"),
+        "{:?}",
+        loaded.problems
+    );
+    assert!(store.path().exists());
+    assert!(!directory.join("high_scores").exists());
+
+    let reloaded = store.load();
+    assert_eq!(reloaded.scores, loaded.scores);
+    assert!(reloaded.problems.is_empty(), "{:?}", reloaded.problems);
+}
+
+#[test]
+fn corrupt_scores_fall_back_to_the_backup() {
+    let store = HighScoreStore::new(empty_directory("backup"));
+    store.save(&scores(&[("xithedwarf", 12)])).unwrap();
+    store.save(&scores(&[("xithedwarf", 20)])).unwrap();
+    fs::write(store.path(), "{\"version\": 1, \"scores\": {\"xith").unwrap();
+
+    let loaded = store.load();
+
+    assert_eq!(loaded.scores, scores(&[("xithedwarf", 12)]));
+    assert_eq!(loaded.problems.len(), 2, "{:?}", loaded.problems);
+}
+
+#[test]
+fn legacy_parsing_keeps_the_best_score_for_repeated_names() {
+    let loaded = parse_legacy_scores("xithedwarf:3\nxithedwarf:9\n\nxithedwarf:5\n");
+
+    assert_eq!(loaded.scores, scores(&[("xithedwarf", 9)]));
+    assert!(loaded.problems.is_empty());
+}
@@ -0,0 +1,114 @@
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+use get_the_streamer_game::hitbox::{Hitbox, HitboxShape, PixelMask};
+use ggez::graphics::Rect;
+
+/// Whether `hitbox` on a sprite at `location` touches a plain hitbox at `other`
+fn touches(hitbox: &Hitbox, location: Rect, rotation: f32, other: Rect) -> bool {
+    hitbox
+        .place(location, rotation)
+        .overlaps(&Hitbox::default().place(other, 0.0))
+}
+
+#[test]
+fn insets_leave_the_edges_of_the_sprite_out() {
+    let snake = Hitbox::new(HitboxShape::Inset {
+        left: 0.0,
+        right: 0.0,
+        top: 0.5,
+        bottom: 0.0,
+    });
+    let location = Rect::new(0.0, 100.0, 48.0, 48.0);
+
+    assert!(!touches(
+        &snake,
+        location,
+        0.0,
+        Rect::new(0.0, 0.0, 48.0, 110.0)
+    ));
+    assert!(touches(
+        &snake,
+        location,
+        0.0,
+        Rect::new(0.0, 0.0, 48.0, 130.0)
+    ));
+}
+
+#[test]
+fn circles_miss_the_corners_of_the_sprite() {
+    let fire = Hitbox::new(HitboxShape::Circle { radius: 0.5 });
+    let location = Rect::new(100.0, 100.0, 50.0, 50.0);
+
+    assert!(!touches(
+        &fire,
+        location,
+        0.0,
+        Rect::new(0.0, 0.0, 105.0, 105.0)
+    ));
+    assert!(touches(
+        &fire,
+        location,
+        0.0,
+        Rect::new(0.0, 0.0, 105.0, 126.0)
+    ));
+}
+
+#[test]
+fn oriented_boxes_turn_with_the_sprite() {
+    // a thin blade from the bottom left to the top right of the sprite
+    let sword = Hitbox::new(HitboxShape::Oriented {
+        width: 1.2,
+        height: 0.2,
+        angle: -45.0,
+    });
+    let location = Rect::new(100.0, 100.0, 48.0, 48.0);
+    let top_left_corner = Rect::new(90.0, 90.0, 15.0, 15.0);
+    let left_side = Rect::new(90.0, 120.0, 15.0, 8.0);
+
+    assert!(!touches(&sword, location, 0.0, top_left_corner));
+    assert!(!touches(&sword, location, 0.0, left_side));
+    // turned a quarter of the way around the blade points at the top left corner
+    assert!(touches(&sword, location, PI / 2.0, top_left_corner));
+    assert!(touches(&sword, location, PI / 4.0, left_side));
+}
+
+#[test]
+fn pixel_masks_skip_the_see_through_pixels() {
+    // two frames of 2 by 2 pixels, the top left pixel is solid in the first frame and the
+    // bottom right one in the second
+    let mut rgba = vec![0; 4 * 2 * 4];
+    let alpha = |x: usize, y: usize| (y * 4 + x) * 4 + 3;
+    rgba[alpha(0, 0)] = 255;
+    rgba[alpha(3, 1)] = 255;
+    let hitbox = Hitbox {
+        shape: HitboxShape::Bounds,
+        mask: Some(Arc::new(PixelMask::from_rgba8(4, 2, &rgba, 2, 1))),
+    };
+    let location = Rect::new(0.0, 0.0, 20.0, 20.0);
+
+    assert!(touches(
+        &hitbox,
+        location,
+        0.0,
+        Rect::new(0.0, 0.0, 5.0, 5.0)
+    ));
+    assert!(touches(
+        &hitbox,
+        location,
+        0.0,
+        Rect::new(15.0, 15.0, 5.0, 5.0)
+    ));
+    assert!(!touches(
+        &hitbox,
+        location,
+        0.0,
+        Rect::new(15.0, 0.0, 5.0, 5.0)
+    ));
+    assert!(!touches(
+        &hitbox,
+        location,
+        0.0,
+        Rect::new(0.0, 15.0, 5.0, 5.0)
+    ));
+}
@@ -0,0 +1,232 @@
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::command::Command;
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::hitbox::HitboxShape;
+use get_the_streamer_game::object_catalog::{
+    InteractionOutcome, ObjectCatalog, PhysicsPreset, PlayerEffect,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const BOULDER: &str = r#"
+[[objects]]
+name = "boulder"
+aliases = ["boulder", "rock"]
+weight = 1
+effect = "damage"
+scale = 4.0
+sprite = { path = "/boulder.png", frames_across = 1, frames_down = 1, frame_width = 20, frame_height = 20 }
+physics = { preset = "fall_and_stay" }
+lifetime = { rule = "timed", seconds = 2.5 }
+"#;
+
+fn create_command(message: &str, catalog: &ObjectCatalog) -> Option<Command> {
+    Command::new(
+        message,
+        Chatter::new("xithedwarf".to_owned(), (0, 0, 0), false),
+        &Config::default(),
+        catalog,
+        &mut StdRng::seed_from_u64(1337),
+    )
+    .unwrap()
+}
+
+#[test]
+fn built_in_catalog_has_the_classic_objects() {
+    let catalog = ObjectCatalog::default();
+
+    for name in &["fire", "sword", "snake", "heart"] {
+        assert!(catalog.get(name).is_some(), "{} is missing", name);
+    }
+    assert_eq!(catalog.find_by_alias("snek").unwrap().name, "snake");
+    assert_eq!(catalog.get("heart").unwrap().effect, PlayerEffect::Heal);
+    assert_eq!(catalog.get("fire").unwrap().get_size(), (52.0, 52.0));
+}
+
+#[test]
+fn commands_drop_objects_from_a_custom_catalog() {
+    let catalog = ObjectCatalog::from_toml(BOULDER).unwrap();
+
+    let command = create_command("#rock 3", &catalog).unwrap();
+    assert_eq!(command.object.name, "boulder");
+    assert_eq!(command.id, 3);
+    assert_eq!(
+        create_command("#random", &catalog).unwrap().object.name,
+        "boulder"
+    );
+    assert!(create_command("#fire", &catalog).is_none());
+}
+
+#[test]
+fn random_never_picks_objects_without_weight() {
+    let raw_catalog = format!(
+        "{}{}",
+        include_str!("../assets/objects.toml"),
+        BOULDER.replace("weight = 1", "weight = 0")
+    );
+    let catalog = ObjectCatalog::from_toml(&raw_catalog).unwrap();
+    let mut rng = StdRng::seed_from_u64(1337);
+
+    for _ in 0..1000 {
+        assert_ne!(catalog.choose_random(&mut rng).name, "boulder");
+    }
+}
+
+#[test]
+fn broken_objects_are_named_in_the_error() {
+    let reused_alias = format!(
+        "{}{}",
+        BOULDER,
+        BOULDER.replace("\"boulder\"", "\"pebble\"")
+    );
+    let error = ObjectCatalog::from_toml(&reused_alias).unwrap_err();
+    assert!(error.to_string().contains("pebble"), "{}", error);
+    assert!(error.to_string().contains("rock"), "{}", error);
+
+    let error = ObjectCatalog::from_toml(&BOULDER.replace("\"rock\"", "\"random\"")).unwrap_err();
+    assert!(error.to_string().contains("boulder"), "{}", error);
+
+    let error =
+        ObjectCatalog::from_toml(&BOULDER.replace("fall_and_stay", "teleport")).unwrap_err();
+    assert!(error.to_string().contains("teleport"), "{}", error);
+}
+
+#[test]
+fn numbers_in_objects_have_to_be_finite() {
+    let broken = [
+        ("seconds = 2.5", "seconds = inf", "lifetime.seconds"),
+        ("seconds = 2.5", "seconds = nan", "lifetime.seconds"),
+        ("scale = 4.0", "scale = inf", "scale"),
+        ("frame_width = 20", "frame_width = inf", "sprite"),
+        (
+            r#"{ preset = "fall_and_stay" }"#,
+            r#"{ preset = "float_down", gravity_scale = inf }"#,
+            "physics.gravity_scale",
+        ),
+    ];
+    for (from, to, key) in broken.iter() {
+        let error = ObjectCatalog::from_toml(&BOULDER.replace(from, to)).unwrap_err();
+        assert!(error.to_string().contains(key), "{}: {}", to, error);
+    }
+
+    let error = ObjectCatalog::from_toml(&format!(
+        "{}hitbox = {{ shape = \"circle\", radius = inf }}\n",
+        BOULDER
+    ))
+    .unwrap_err();
+    assert!(error.to_string().contains("hitbox.radius"), "{}", error);
+}
+
+#[test]
+fn lifetimes_need_a_preset_that_ends_them() {
+    let timed = r#"{ rule = "timed", seconds = 2.5 }"#;
+    let fall_and_stay = r#"{ preset = "fall_and_stay" }"#;
+    let broken = [
+        // nothing counts the bounces
+        (
+            fall_and_stay,
+            r#"{ rule = "bounces", count = 3 }"#,
+            "damage",
+            "bounces",
+        ),
+        // nothing picks it up
+        (
+            fall_and_stay,
+            r#"{ rule = "until_picked_up" }"#,
+            "damage",
+            "until_picked_up",
+        ),
+        (
+            fall_and_stay,
+            r#"{ rule = "until_picked_up" }"#,
+            "heal",
+            "until_picked_up",
+        ),
+        // the streamer would heal on every tick they touch it
+        (
+            r#"{ preset = "float_down", gravity_scale = 0.25 }"#,
+            timed,
+            "heal",
+            "heal",
+        ),
+    ];
+    for (physics, lifetime, effect, requirement) in broken.iter() {
+        let object = BOULDER
+            .replace(fall_and_stay, physics)
+            .replace(timed, lifetime)
+            .replace("\"damage\"", &format!("\"{}\"", effect));
+        let error = ObjectCatalog::from_toml(&object).unwrap_err();
+        assert!(error.to_string().contains(requirement), "{}", error);
+    }
+
+    let heart = BOULDER
+        .replace(
+            fall_and_stay,
+            r#"{ preset = "float_down", gravity_scale = 0.25 }"#,
+        )
+        .replace(timed, r#"{ rule = "until_picked_up" }"#)
+        .replace("\"damage\"", "\"heal\"");
+    assert!(ObjectCatalog::from_toml(&heart).is_ok());
+}
+
+#[test]
+fn per_tick_catalogs_are_converted_to_per_second() {
+    let catalog = ObjectCatalog::from_toml(&BOULDER.replace(
+        r#"{ preset = "fall_and_stay" }"#,
+        r#"{ preset = "bounce", max_bounce_speed = 15, bounciness = 0.5 }"#,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        catalog.get("boulder").unwrap().physics,
+        PhysicsPreset::Bounce {
+            max_bounce_speed: 900.0,
+            bounciness: 0.5
+        }
+    );
+}
+
+#[test]
+fn objects_can_have_a_smaller_hitbox_than_their_sprite() {
+    let catalog = ObjectCatalog::from_toml(BOULDER).unwrap();
+    assert_eq!(catalog.get("boulder").unwrap().hitbox, HitboxShape::Bounds);
+
+    let catalog = ObjectCatalog::from_toml(&format!(
+        "{}hitbox = {{ shape = \"inset\", left = 0.25, top = 0.5 }}\n",
+        BOULDER
+    ))
+    .unwrap();
+    assert_eq!(
+        catalog.get("boulder").unwrap().hitbox,
+        HitboxShape::Inset {
+            left: 0.25,
+            right: 0.0,
+            top: 0.5,
+            bottom: 0.0
+        }
+    );
+
+    let error = ObjectCatalog::from_toml(&format!(
+        "{}hitbox = {{ shape = \"inset\", left = 0.5, right = 0.5 }}\n",
+        BOULDER
+    ))
+    .unwrap_err();
+    assert!(error.to_string().contains("hitbox"), "{}", error);
+}
+
+#[test]
+fn interactions_need_objects_from_the_catalog() {
+    let catalog = ObjectCatalog::default();
+    assert_eq!(
+        catalog.interaction("sword", "fire"),
+        Some(InteractionOutcome::Destroyed)
+    );
+    assert_eq!(catalog.interaction("fire", "sword"), None);
+
+    let lava = format!(
+        "{}\n[[interactions]]\nobject = \"boulder\"\ntouches = \"lava\"\noutcome = \"destroyed\"\n",
+        BOULDER
+    );
+    let error = ObjectCatalog::from_toml(&lava).unwrap_err();
+    assert!(error.to_string().contains("lava"), "{}", error);
+}
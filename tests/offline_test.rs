@@ -1,25 +1,33 @@
+use get_the_streamer_game::chat_transport::{ChatTransport, IncomingChat, MockChatTransport};
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
 use get_the_streamer_game::GameState;
 use ggez::conf::{WindowMode, WindowSetup};
 use ggez::{event, ContextBuilder};
 use std::sync::mpsc::channel;
 use std::thread;
-use twitch_chat_wrapper::chat_message::ChatMessage;
-mod chat_test_mock;
 
 const WINDOW_SIZE: (f32, f32) = (1920.0, 1080.0);
 
+/// Plays a full round against the chat mock in a real window, so it needs a display.
+/// Run it with `cargo test -- --ignored`, the headless gameplay tests are in `simulation_test.rs`
 #[test]
+#[ignore]
 fn main() {
-    let (send_to_game, receive_from_twitch) = channel::<ChatMessage>();
-    let (send_to_twitch, _receive_from_game) = channel::<String>();
+    let (send_to_game, receive_from_chat) = channel::<IncomingChat>();
+    let (send_to_chat, receive_from_game) = channel::<String>();
+    let mut config = Config::default();
+    // quit after the credits so that the test finishes
+    config.game.session_mode = false;
 
-    chat_test_mock::run(
-        send_to_game,
-        5,
-        get_the_streamer_game::SPLASH_DURATION,
-        250,
-        1500,
-    );
+    let chat_transport = Box::new(MockChatTransport {
+        num_bot_users: 5,
+        initial_commands_delay: config.game.splash_duration(),
+        min_command_interval_ms: 250,
+        max_command_interval_ms: 1500,
+        cheer_chance: 0.0,
+    });
+    thread::spawn(move || chat_transport.run(send_to_game, receive_from_game));
 
     let game_thread = thread::spawn(move || {
         let (context, event_loop) =
@@ -29,11 +37,20 @@ fn main() {
                 .build()
             {
                 Ok((context, event_loop)) => (context, event_loop),
-                Err(error) => panic!(error),
+                Err(error) => panic!("{}", error),
             };
 
-        let game_state =
-            &mut GameState::new(send_to_twitch, receive_from_twitch, WINDOW_SIZE, context).unwrap();
+        let game_state = &mut GameState::new(
+            send_to_chat,
+            receive_from_chat,
+            WINDOW_SIZE,
+            rand::random(),
+            config,
+            ObjectCatalog::default(),
+            None,
+            context,
+        )
+        .unwrap();
         match event::run(context, event_loop, game_state) {
             Ok(_) => println!("Thanks for playing!"),
             Err(error) => println!("Error occurred: {}", error),
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::PathBuf;
+
+use get_the_streamer_game::personal_bests::{
+    add_run, PersonalBestsStore, SurvivalRun, PERSONAL_BESTS_SIZE,
+};
+
+fn empty_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "gts_personal_bests_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::remove_dir_all(&directory).ok();
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn run(seconds: u64) -> SurvivalRun {
+    SurvivalRun {
+        seconds,
+        wave: seconds as u32 / 30 + 1,
+        ended_at: 1_600_000_000 + seconds,
+        top_scorer: Some("xithedwarf".to_owned()),
+    }
+}
+
+#[test]
+fn runs_survive_a_save_and_load() {
+    let store = PersonalBestsStore::new(empty_directory("round_trip"));
+    let mut runs = vec![];
+    add_run(&mut runs, run(95));
+    add_run(&mut runs, run(205));
+
+    store.save(&runs).unwrap();
+    let loaded = store.load();
+
+    assert_eq!(loaded.runs, vec![run(205), run(95)]);
+    assert!(loaded.problems.is_empty(), "{:?}", loaded.problems);
+    assert_eq!(loaded.runs[0].format_time(), "3:25");
+}
+
+#[test]
+fn only_the_longest_runs_are_kept() {
+    let mut runs: Vec<SurvivalRun> = (1..=PERSONAL_BESTS_SIZE as u64)
+        .rev()
+        .map(|minutes| run(minutes * 60))
+        .collect();
+
+    assert_eq!(add_run(&mut runs, run(30)), None);
+    assert_eq!(add_run(&mut runs, run(150)), Some(PERSONAL_BESTS_SIZE - 2));
+    assert_eq!(runs.len(), PERSONAL_BESTS_SIZE);
+    assert_eq!(runs.last(), Some(&run(120)));
+    // a tie doesn't beat the run that got there first
+    assert_eq!(add_run(&mut runs, run(600)), Some(1));
+}
+
+#[test]
+fn missing_file_is_an_empty_table() {
+    let loaded = PersonalBestsStore::new(empty_directory("missing")).load();
+
+    assert!(loaded.runs.is_empty());
+    assert!(loaded.problems.is_empty(), "{:?}", loaded.problems);
+}
@@ -0,0 +1,123 @@
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::input_state::InputState;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::replay::{Replay, ReplayChatMessage};
+use get_the_streamer_game::Simulation;
+use std::fs;
+use std::sync::mpsc::channel;
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+const SEED: u64 = 42;
+
+fn create_chat_message(tick: u64, message: &str) -> ReplayChatMessage {
+    ReplayChatMessage {
+        tick,
+        name: "test_bot1".to_owned(),
+        color: (255, 0, 0),
+        subscriber: true,
+        badges: vec![],
+        bits: 0,
+        custom_reward_id: None,
+        message: message.to_owned(),
+    }
+}
+
+fn get_positions(simulation: &Simulation) -> Vec<(f32, f32)> {
+    simulation
+        .game_objects()
+        .iter()
+        .map(|game_object| (game_object.location.x, game_object.location.y))
+        .collect()
+}
+
+#[test]
+fn replay_plays_the_same_game_as_the_recording() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        Config::default(),
+        ObjectCatalog::default(),
+    );
+    let mut recording = Replay::new(SEED, Config::default(), ObjectCatalog::default());
+    for tick in 0..1200 {
+        let input = InputState {
+            right: tick % 200 < 100,
+            left: tick % 200 >= 100,
+            jump: tick % 50 == 0,
+            ..InputState::default()
+        };
+        let chat_messages = if tick > 900 && tick % 10 == 0 {
+            vec![create_chat_message(tick, "#random")]
+        } else {
+            vec![]
+        };
+        recording.record(input, &chat_messages);
+        let commands = simulation.create_commands(&chat_messages);
+        simulation.tick(&input, commands);
+    }
+
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut replayed_simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        recording.seed,
+        recording.config.clone(),
+        recording.catalog.clone(),
+    );
+    for tick in 0..1200 {
+        let chat_messages = recording.take_chat_messages(tick);
+        let commands = replayed_simulation.create_commands(&chat_messages);
+        replayed_simulation.tick(&recording.get_input(tick), commands);
+    }
+
+    assert!(simulation.game_objects().len() > 1);
+    assert_eq!(
+        get_positions(&simulation),
+        get_positions(&replayed_simulation)
+    );
+}
+
+#[test]
+fn chat_messages_are_taken_once_in_tick_order() {
+    let mut replay = Replay::new(SEED, Config::default(), ObjectCatalog::default());
+    replay.record(InputState::default(), &[create_chat_message(0, "#fire 1")]);
+    replay.record(
+        InputState::default(),
+        &[
+            create_chat_message(1, "#sword 2"),
+            create_chat_message(1, "#snake 3"),
+        ],
+    );
+
+    assert_eq!(replay.take_chat_messages(0).len(), 1);
+    assert_eq!(replay.take_chat_messages(0).len(), 0);
+    let chat_messages = replay.take_chat_messages(1);
+    assert_eq!(chat_messages[1].message, "#snake 3");
+    assert!(replay.get_input(5) == InputState::default());
+}
+
+#[test]
+fn replay_files_are_loaded() {
+    let path = std::env::temp_dir().join("get_the_streamer_replay_test.json");
+    fs::write(
+        &path,
+        r##"{"version":2,"seed":7,"inputs":[{"left":true,"right":false,"jump":false}],
+        "chat_messages":[{"tick":0,"name":"test_bot1","color":[1,2,3],"subscriber":false,"message":"#heart"}]}"##,
+    )
+    .unwrap();
+    let mut replay = Replay::load(&path).unwrap();
+
+    assert_eq!(replay.seed, 7);
+    assert!(replay.get_input(0).left);
+    assert_eq!(replay.take_chat_messages(0)[0].color, (1, 2, 3));
+
+    fs::write(
+        &path,
+        r#"{"version":99,"seed":7,"inputs":[],"chat_messages":[]}"#,
+    )
+    .unwrap();
+    assert!(Replay::load(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,170 @@
+use get_the_streamer_game::chat_transport::Scenario;
+use get_the_streamer_game::config::{Config, PremiumDrop};
+use get_the_streamer_game::input_state::InputState;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::replay::ReplayChatMessage;
+use get_the_streamer_game::running_state::RunningState;
+use get_the_streamer_game::Simulation;
+use std::sync::mpsc::channel;
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+const SEED: u64 = 1337;
+
+/// Plays one round headless with chat coming from the scenario, the streamer never moves.
+/// Returns the finished simulation and everything the game said in chat.
+fn play_scenario(file_name: &str) -> (Simulation, Vec<String>) {
+    play_scenario_with_config(file_name, Config::default())
+}
+
+fn play_scenario_with_config(file_name: &str, config: Config) -> (Simulation, Vec<String>) {
+    let mut scenario = Scenario::load(format!("tests/scenarios/{}", file_name)).unwrap();
+    let (send_to_chat, receive_from_game) = channel();
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+
+    while simulation.running_state() == RunningState::StartingSoon {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+    let round_started_at = simulation.elapsed();
+    while simulation.running_state() == RunningState::Playing {
+        let tick = simulation.ticks();
+        let chat_messages: Vec<ReplayChatMessage> = scenario
+            .take_due(simulation.elapsed() - round_started_at)
+            .into_iter()
+            .map(|chat| ReplayChatMessage::new(tick, chat))
+            .collect();
+        let commands = simulation.create_commands(&chat_messages);
+        simulation.tick(&InputState::default(), commands);
+    }
+
+    assert!(
+        scenario.is_finished(),
+        "the round ended before the scenario"
+    );
+    (simulation, receive_from_game.try_iter().collect())
+}
+
+#[test]
+fn twenty_swords_into_column_3() {
+    let mut config = Config::default();
+    config.limits.drop_cooldown_seconds = 0.0;
+    let (simulation, _chat) = play_scenario_with_config("swords_into_column_3.txt", config);
+
+    // the round is seeded, so the same three swords land every time
+    let stats = &simulation.round_stats()["xithedwarf"];
+    assert_eq!(stats.drops.get("sword"), Some(&20));
+    assert_eq!(stats.hits_landed, 3);
+    // a point for every drop and ten for every hit
+    assert_eq!(simulation.scores()["xithedwarf"], 20 + 3 * 10);
+}
+
+#[test]
+fn a_macro_only_gets_one_sword_through_the_cooldown() {
+    let (simulation, chat) = play_scenario("swords_into_column_3.txt");
+
+    let stats = &simulation.round_stats()["xithedwarf"];
+    assert_eq!(stats.drops.get("sword"), Some(&1));
+    let refusals: Vec<&String> = chat
+        .iter()
+        .filter(|message| message.starts_with("@xithedwarf slow down"))
+        .collect();
+    assert_eq!(refusals.len(), 1, "{:?}", chat);
+}
+
+#[test]
+fn malformed_commands_from_a_subscriber_drop_nothing() {
+    let (simulation, chat) = play_scenario("malformed_subscriber_commands.txt");
+
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+    assert!(simulation.scores().is_empty());
+    assert!(chat.contains(&"The given column is outside of the arena".to_owned()));
+    assert!(chat.contains(&"I couldn't tell what column to drop into".to_owned()));
+}
+
+#[test]
+fn fire_under_the_streamer_wins_for_chat() {
+    let (simulation, chat) = play_scenario("fire_under_the_streamer.txt");
+
+    assert!(simulation.running_state() == RunningState::ChatWon);
+    assert_eq!(
+        simulation.get_highest_scorer().map(|(name, _score)| name),
+        Some("ootsby".to_owned())
+    );
+    assert_eq!(simulation.scores().get("dmb1107"), Some(&1));
+    assert!(chat.last().unwrap().contains("highest scorer was ootsby"));
+}
+
+#[test]
+fn cheers_and_rewards_get_premium_drops() {
+    let mut config = Config::default();
+    config.premium.drops.push(PremiumDrop {
+        name: "a handful of hearts".to_owned(),
+        object: "heart".to_owned(),
+        count: 3,
+        scale: 1.0,
+        min_bits: None,
+        max_bits: None,
+        reward_id: Some("heart-reward".to_owned()),
+    });
+    let (simulation, chat) = play_scenario_with_config("cheers_and_rewards.txt", config);
+
+    let stats = simulation.round_stats();
+    // premium drops skip the cooldown between drops
+    assert_eq!(stats["brookzerker"].drops.get("snake"), Some(&5));
+    assert_eq!(stats["brookzerker"].drops.get("sword"), Some(&1));
+    assert_eq!(stats["ootsby"].drops.get("heart"), Some(&8));
+    assert_eq!(stats["dmb1107"].drops.get("heart"), Some(&3));
+    // 50 bits isn't enough for anything, so it's a normal drop
+    assert_eq!(stats["dmb1107"].drops.get("fire"), Some(&1));
+    assert!(chat.contains(&"brookzerker cheered 100 bits for a swarm of snakes!".to_owned()));
+    assert!(chat.contains(&"dmb1107 redeemed a handful of hearts!".to_owned()));
+}
+
+#[test]
+fn scenario_lines_must_be_complete() {
+    let error = Scenario::parse("# comment\n\n1.0 xithedwarf #FF0000 maybe #fire 3\n").unwrap_err();
+    assert!(error.to_string().contains("line 3"), "{}", error);
+
+    let error = Scenario::parse("1.0 xithedwarf\n").unwrap_err();
+    assert!(error.to_string().contains("color"), "{}", error);
+
+    let scenario = Scenario::parse("2.0 a #000000 - #fire 1\n1.0 b #FFFFFF sub #sword\n").unwrap();
+    let first = &scenario.messages()[0];
+    assert_eq!(first.chat.name, "b");
+    assert_eq!(first.chat.color, (255, 255, 255));
+    assert!(first.chat.subscriber);
+    assert_eq!(first.chat.message, "#sword");
+
+    let scenario =
+        Scenario::parse("1.0 a #000000 sub,bits=100,reward=abc,badge=moderator Cheer100\n")
+            .unwrap();
+    let chat = &scenario.messages()[0].chat;
+    assert!(chat.subscriber);
+    assert_eq!(chat.bits, 100);
+    assert_eq!(chat.custom_reward_id.as_deref(), Some("abc"));
+    assert!(chat.has_badge("moderator"));
+}
+
+#[test]
+fn scenario_times_have_to_be_finite() {
+    for time in &["nan", "inf", "-1.0"] {
+        let line = format!("# comment\n{} xithedwarf #FF0000 - #fire 3\n", time);
+        let error = Scenario::parse(&line).unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{}", error);
+    }
+}
+
+#[test]
+fn extra_spaces_between_fields_are_ignored() {
+    let scenario = Scenario::parse("1.0  xithedwarf\t#FF0000   sub  #sword  3\n").unwrap();
+    let chat = &scenario.messages()[0].chat;
+    assert_eq!(chat.name, "xithedwarf");
+    assert_eq!(chat.color, (255, 0, 0));
+    assert!(chat.subscriber);
+    assert_eq!(chat.message, "#sword  3");
+}
@@ -0,0 +1,688 @@
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::command::Command;
+use get_the_streamer_game::config::Config;
+use get_the_streamer_game::input_state::{InputState, PlayerInput, PlayerNumber};
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::replay::ReplayChatMessage;
+use get_the_streamer_game::running_state::RunningState;
+use get_the_streamer_game::team::Team;
+use get_the_streamer_game::Simulation;
+use ggez::graphics::Rect;
+use std::sync::mpsc::{channel, Receiver};
+
+const ARENA_SIZE: (f32, f32) = (1328.0, 1080.0);
+const SEED: u64 = 1337;
+
+fn create_simulation() -> (Simulation, Receiver<String>) {
+    let (send_to_chat, receive_from_game) = channel();
+    (
+        Simulation::new(
+            send_to_chat,
+            ARENA_SIZE,
+            SEED,
+            Config::default(),
+            ObjectCatalog::default(),
+        ),
+        receive_from_game,
+    )
+}
+
+fn create_session_simulation() -> (Simulation, Receiver<String>) {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.game.session_mode = true;
+    (
+        Simulation::new(
+            send_to_chat,
+            ARENA_SIZE,
+            SEED,
+            config,
+            ObjectCatalog::default(),
+        ),
+        receive_from_game,
+    )
+}
+
+fn create_command(simulation: &mut Simulation, message: &str, name: &str) -> Command {
+    Command::new(
+        message,
+        Chatter::new(name.to_owned(), (0, 0, 0), false),
+        &Config::default(),
+        &ObjectCatalog::default(),
+        simulation.rng(),
+    )
+    .unwrap()
+    .unwrap()
+}
+
+fn create_chat_message(simulation: &Simulation, name: &str, message: &str) -> ReplayChatMessage {
+    ReplayChatMessage {
+        tick: simulation.ticks(),
+        name: name.to_owned(),
+        color: (0, 0, 0),
+        subscriber: false,
+        badges: vec![],
+        bits: 0,
+        custom_reward_id: None,
+        message: message.to_owned(),
+    }
+}
+
+fn tick_for(simulation: &mut Simulation, ticks: u32) {
+    for _ in 0..ticks {
+        simulation.tick(&InputState::default(), vec![]);
+    }
+}
+
+fn tick_until_playing(simulation: &mut Simulation) {
+    let game = Config::default().game;
+    tick_for(simulation, game.duration_to_ticks(game.splash_duration()));
+    assert!(simulation.running_state() == RunningState::Playing);
+}
+
+#[test]
+fn splash_screen_runs_before_the_game_starts() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+
+    tick_for(&mut simulation, 1);
+
+    assert!(simulation.running_state() == RunningState::StartingSoon);
+    tick_until_playing(&mut simulation);
+}
+
+#[test]
+fn drops_are_ignored_before_the_game_starts() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+
+    let command = create_command(&mut simulation, "#sword 3", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+
+    assert_eq!(simulation.game_objects().len(), 1);
+    assert!(simulation.scores().is_empty());
+}
+
+#[test]
+fn dropping_an_object_scores_a_point() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+
+    let command = create_command(&mut simulation, "#sword 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+
+    assert_eq!(simulation.game_objects().len(), 2);
+    assert_eq!(simulation.scores().get("test_bot1"), Some(&1));
+}
+
+#[test]
+fn player_walks_with_input() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+    let start_x = simulation.get_player(PlayerNumber::One).unwrap().location.x;
+
+    let walk_right = InputState {
+        right: true,
+        ..InputState::default()
+    };
+    for _ in 0..10 {
+        simulation.tick(&walk_right, vec![]);
+    }
+
+    assert!(simulation.get_player(PlayerNumber::One).unwrap().location.x > start_x);
+}
+
+#[test]
+fn player_wins_when_time_runs_out() {
+    let (mut simulation, receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+
+    let game = Config::default().game;
+    tick_for(&mut simulation, game.duration_to_ticks(game.game_time()));
+
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+    let last_message = receive_from_game.try_iter().last().unwrap();
+    assert!(last_message.starts_with("The Streamer won the game"));
+}
+
+#[test]
+fn chat_wins_when_the_player_runs_out_of_lives() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+
+    // the player is standing under column 2, where the fire will stay burning
+    let command = create_command(&mut simulation, "#fire 2", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+    tick_for(&mut simulation, 6 * Config::default().game.framerate_target);
+
+    assert!(simulation.running_state() == RunningState::ChatWon);
+    assert_eq!(simulation.player_lives_left(PlayerNumber::One), 0);
+    assert_eq!(simulation.scores().get("test_bot1"), Some(&31));
+
+    let stats = &simulation.round_stats()["test_bot1"];
+    assert_eq!(stats.drops.get("fire"), Some(&1));
+    assert_eq!(stats.hits_landed, 3);
+    assert_eq!(stats.rounds_played, 1);
+    assert_eq!(stats.rounds_won_by_chat, 1);
+}
+
+#[test]
+fn same_seed_plays_the_same_game() {
+    let play = || {
+        let (mut simulation, _receive_from_game) = create_simulation();
+        tick_until_playing(&mut simulation);
+        for _ in 0..20 {
+            let command = create_command(&mut simulation, "#random", "test_bot1");
+            simulation.tick(&InputState::default(), vec![command]);
+        }
+        tick_for(&mut simulation, 120);
+        simulation
+            .game_objects()
+            .iter()
+            .map(|game_object| (game_object.location.x, game_object.location.y))
+            .collect::<Vec<(f32, f32)>>()
+    };
+
+    assert_eq!(play(), play());
+}
+
+#[test]
+fn session_starts_a_fresh_round_after_the_intermission() {
+    let (mut simulation, _receive_from_game) = create_session_simulation();
+    tick_until_playing(&mut simulation);
+    let command = create_command(&mut simulation, "#fire 2", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+    tick_for(&mut simulation, 6 * Config::default().game.framerate_target);
+    assert!(simulation.running_state() == RunningState::ChatWon);
+
+    let game = Config::default().game;
+    tick_for(&mut simulation, game.duration_to_ticks(game.intermission()));
+
+    assert!(simulation.running_state() == RunningState::StartingSoon);
+    assert_eq!(simulation.round(), 2);
+    assert!(simulation.scores().is_empty());
+    assert_eq!(simulation.game_objects().len(), 1);
+    assert_eq!(simulation.player_lives_left(PlayerNumber::One), game.lives);
+    assert_eq!(
+        simulation.get_session_leaderboard(),
+        vec![("test_bot1".to_owned(), 31)]
+    );
+}
+
+#[test]
+fn hotkey_starts_the_next_round_early() {
+    let (mut simulation, _receive_from_game) = create_session_simulation();
+    let start_next_round = InputState {
+        start_next_round: true,
+        ..InputState::default()
+    };
+
+    simulation.tick(&start_next_round, vec![]);
+    assert!(simulation.running_state() == RunningState::Playing);
+
+    let game = Config::default().game;
+    tick_for(&mut simulation, game.duration_to_ticks(game.game_time()));
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+
+    simulation.tick(&start_next_round, vec![]);
+    assert!(simulation.running_state() == RunningState::Playing);
+    assert_eq!(simulation.round(), 2);
+}
+
+#[test]
+fn game_stays_over_outside_of_session_mode() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut config = Config::default();
+    config.game.session_mode = false;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config.clone(),
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+    tick_for(
+        &mut simulation,
+        config.game.duration_to_ticks(config.game.game_time()),
+    );
+
+    tick_for(
+        &mut simulation,
+        config.game.duration_to_ticks(config.game.intermission()) * 2,
+    );
+
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+    assert_eq!(simulation.round(), 1);
+}
+
+#[test]
+fn hearts_have_their_own_cooldown() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+    let config = Config::default();
+
+    let command = create_command(&mut simulation, "#heart 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+    tick_for(
+        &mut simulation,
+        config
+            .game
+            .seconds_to_ticks(config.limits.drop_cooldown_seconds) as u32,
+    );
+    let heart = create_command(&mut simulation, "#heart 9", "test_bot1");
+    let sword = create_command(&mut simulation, "#sword 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![heart, sword]);
+
+    let drops = &simulation.round_stats()["test_bot1"].drops;
+    assert_eq!(drops.get("heart"), Some(&1));
+    assert_eq!(drops.get("sword"), Some(&1));
+}
+
+#[test]
+fn cooldowns_too_long_to_count_in_ticks_still_refuse_drops() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut config = Config::default();
+    config.limits.drop_cooldown_seconds = 1e30;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+
+    let command = create_command(&mut simulation, "#sword 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+    let command = create_command(&mut simulation, "#sword 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+
+    assert_eq!(
+        simulation.round_stats()["test_bot1"].drops.get("sword"),
+        Some(&1)
+    );
+}
+
+#[test]
+fn drops_are_refused_while_the_arena_is_full() {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.limits.max_live_enemies = 2;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+
+    let commands = vec![
+        create_command(&mut simulation, "#sword 7", "test_bot1"),
+        create_command(&mut simulation, "#sword 8", "test_bot2"),
+        create_command(&mut simulation, "#sword 9", "test_bot3"),
+    ];
+    simulation.tick(&InputState::default(), commands);
+
+    assert_eq!(simulation.game_objects().len(), 3);
+    assert!(!simulation.scores().contains_key("test_bot3"));
+    assert!(receive_from_game
+        .try_iter()
+        .any(|message| message.starts_with("The arena is full")));
+}
+
+#[test]
+fn chat_votes_on_the_next_drop_in_voting_mode() {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.voting.enabled = true;
+    config.voting.voters_per_extra_object = 2;
+    let window_ticks = config.game.seconds_to_ticks(config.voting.window_seconds);
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+
+    let chat_messages: Vec<ReplayChatMessage> = [
+        ("test_bot1", "#vote fire 1"),
+        ("test_bot2", "#vote sword 4"),
+        ("test_bot3", "#vote sword 4"),
+        // plain drops are ignored while voting
+        ("test_bot4", "#sword 3"),
+    ]
+    .iter()
+    .map(|(name, message)| create_chat_message(&simulation, name, message))
+    .collect();
+    let commands = simulation.create_commands(&chat_messages);
+    assert!(commands.is_empty());
+    tick_for(&mut simulation, window_ticks as u32 - 1);
+    assert!(simulation.scores().is_empty());
+    simulation.tick(&InputState::default(), vec![]);
+
+    // three voters is one extra sword, both belong to the first chatter who voted for swords
+    let swords = simulation
+        .game_objects()
+        .iter()
+        .filter(|game_object| game_object.object_name.as_deref() == Some("sword"))
+        .count();
+    assert_eq!(swords, 2);
+    assert_eq!(simulation.scores().get("test_bot2"), Some(&2));
+    assert_eq!(simulation.scores().len(), 1);
+    assert!(receive_from_game
+        .try_iter()
+        .any(|message| message == "The vote is in: sword x2 into column 4, 3 voted!"));
+}
+
+#[test]
+fn teams_score_the_points_of_their_chatters() {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.teams.enabled = true;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config.clone(),
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+
+    let chat_messages: Vec<ReplayChatMessage> = [
+        ("test_bot1", "!join blue"),
+        ("test_bot2", "!join blue"),
+        ("test_bot1", "!join red"),
+        // nobody is on red yet, so that's where this chatter goes
+        ("test_bot3", "#fire 9"),
+        ("test_bot1", "#fire 8"),
+        ("test_bot2", "#fire 7"),
+    ]
+    .iter()
+    .map(|(name, message)| create_chat_message(&simulation, name, message))
+    .collect();
+    let commands = simulation.create_commands(&chat_messages);
+    assert!(commands
+        .iter()
+        .all(|command| command.chatter.team.is_none()));
+    simulation.tick(&InputState::default(), commands);
+
+    let teams: Vec<_> = simulation
+        .game_objects()
+        .iter()
+        .filter_map(|game_object| game_object.chatter.as_ref())
+        .map(|chatter| (chatter.name.as_str(), chatter.team))
+        .collect();
+    assert!(teams.contains(&("test_bot3", Some(Team::Red))));
+    assert!(teams.contains(&("test_bot1", Some(Team::Blue))));
+    assert_eq!(
+        simulation.team_scores(),
+        vec![(Team::Blue, 2), (Team::Red, 1)]
+    );
+    assert_eq!(simulation.winning_team(), Some(Team::Blue));
+
+    tick_for(
+        &mut simulation,
+        config.game.duration_to_ticks(config.game.game_time()),
+    );
+    let chat: Vec<String> = receive_from_game.try_iter().collect();
+    assert!(chat.contains(&"@test_bot1 is already on the blue team".to_owned()));
+    assert!(
+        chat.last()
+            .unwrap()
+            .ends_with("The blue team wins with 2 points to 1!"),
+        "{:?}",
+        chat
+    );
+}
+
+#[test]
+fn endless_mode_keeps_going_in_harder_waves() {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.endless.enabled = true;
+    let wave_ticks = config.game.duration_to_ticks(config.endless.wave_time());
+    let gravity_force = config.physics.gravity_force;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+    assert_eq!(simulation.wave(), Some(1));
+
+    let game = Config::default().game;
+    tick_for(&mut simulation, game.duration_to_ticks(game.game_time()));
+
+    // 120 seconds is four 30 second waves, the round never runs out of time
+    assert!(simulation.running_state() == RunningState::Playing);
+    assert_eq!(simulation.wave(), Some(5));
+    assert_eq!(simulation.survival_time().as_secs(), 120);
+    assert!(receive_from_game
+        .try_iter()
+        .any(|message| message == "Wave 5! Everything just got faster"));
+    assert!(simulation.config().for_wave(5).physics.gravity_force > gravity_force);
+
+    tick_for(&mut simulation, wave_ticks);
+    assert_eq!(simulation.wave(), Some(6));
+}
+
+#[test]
+fn coop_streamers_move_on_their_own_keys_and_chat_has_to_get_both() {
+    let (send_to_chat, _receive_from_game) = channel();
+    let mut config = Config::default();
+    config.coop.enabled = true;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+    tick_until_playing(&mut simulation);
+    let player_one_x = simulation.get_player(PlayerNumber::One).unwrap().location.x;
+    let player_two_x = simulation.get_player(PlayerNumber::Two).unwrap().location.x;
+
+    let walk_player_two_right = InputState {
+        player_two: PlayerInput {
+            right: true,
+            ..PlayerInput::default()
+        },
+        ..InputState::default()
+    };
+    for _ in 0..10 {
+        simulation.tick(&walk_player_two_right, vec![]);
+    }
+    assert_eq!(
+        simulation.get_player(PlayerNumber::One).unwrap().location.x,
+        player_one_x
+    );
+    assert!(simulation.get_player(PlayerNumber::Two).unwrap().location.x > player_two_x);
+
+    // the first streamer stands under column 2, the second walked right and slides on
+    // until they're under the edge of column 4
+    let framerate_target = Config::default().game.framerate_target;
+    let command = create_command(&mut simulation, "#fire 2", "test_bot1");
+    simulation.tick(&InputState::default(), vec![command]);
+    tick_for(&mut simulation, 6 * framerate_target);
+    assert!(simulation.get_player(PlayerNumber::One).is_none());
+    assert_eq!(simulation.player_lives_left(PlayerNumber::Two), 3);
+    assert!(simulation.running_state() == RunningState::Playing);
+
+    let command = create_command(&mut simulation, "#fire 4", "test_bot2");
+    simulation.tick(&InputState::default(), vec![command]);
+    tick_for(&mut simulation, 6 * framerate_target);
+    assert!(simulation.running_state() == RunningState::ChatWon);
+}
+
+#[test]
+fn the_streamer_falls_as_fast_at_any_tick_rate() {
+    let fallen_in_half_a_second = |framerate_target: u32| {
+        let (send_to_chat, _receive_from_game) = channel();
+        let mut config = Config::default();
+        config.game.framerate_target = framerate_target;
+        let splash_ticks = config.game.duration_to_ticks(config.game.splash_duration());
+        let mut simulation = Simulation::new(
+            send_to_chat,
+            ARENA_SIZE,
+            SEED,
+            config,
+            ObjectCatalog::default(),
+        );
+        tick_for(&mut simulation, splash_ticks);
+        let start_y = simulation.get_player(PlayerNumber::One).unwrap().location.y;
+        tick_for(&mut simulation, framerate_target / 2);
+        simulation.get_player(PlayerNumber::One).unwrap().location.y - start_y
+    };
+
+    let fallen = fallen_in_half_a_second(60);
+    assert!(fallen > 100.0, "{}", fallen);
+    for framerate_target in &[30, 144] {
+        let difference = (fallen_in_half_a_second(*framerate_target) - fallen).abs();
+        assert!(difference < 10.0, "{} at {}", difference, framerate_target);
+    }
+}
+
+fn objects_named<'a>(simulation: &'a Simulation, name: &str) -> Vec<&'a Rect> {
+    simulation
+        .game_objects()
+        .iter()
+        .filter(|game_object| game_object.object_name.as_deref() == Some(name))
+        .map(|game_object| &game_object.location)
+        .collect()
+}
+
+#[test]
+fn swords_and_snakes_that_land_in_fire_are_destroyed() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+
+    let fire = create_command(&mut simulation, "#fire 9", "test_bot1");
+    simulation.tick(&InputState::default(), vec![fire]);
+    tick_for(&mut simulation, 90);
+    let sword = create_command(&mut simulation, "#sword 9", "test_bot2");
+    let snake = create_command(&mut simulation, "#snake 9", "test_bot3");
+    simulation.tick(&InputState::default(), vec![sword, snake]);
+    assert_eq!(objects_named(&simulation, "sword").len(), 1);
+    assert_eq!(objects_named(&simulation, "snake").len(), 1);
+    tick_for(&mut simulation, 180);
+
+    assert_eq!(objects_named(&simulation, "fire").len(), 1);
+    assert!(objects_named(&simulation, "sword").is_empty());
+    assert!(objects_named(&simulation, "snake").is_empty());
+}
+
+#[test]
+fn swords_that_clash_bounce_apart() {
+    let (mut simulation, _receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+
+    let sword = create_command(&mut simulation, "#sword 5", "test_bot1");
+    simulation.tick(&InputState::default(), vec![sword]);
+    let start_x = objects_named(&simulation, "sword")[0].x;
+    let sword = create_command(&mut simulation, "#sword 5", "test_bot2");
+    simulation.tick(&InputState::default(), vec![sword]);
+    tick_for(&mut simulation, 30);
+
+    let swords = objects_named(&simulation, "sword");
+    assert_eq!(swords.len(), 2);
+    assert!(swords[0].x > start_x, "{} from {}", swords[0].x, start_x);
+    assert!(swords[1].x < start_x, "{} from {}", swords[1].x, start_x);
+}
+
+#[test]
+fn cheers_before_the_round_drop_once_it_starts() {
+    let (mut simulation, receive_from_game) = create_simulation();
+    let cheer = ReplayChatMessage {
+        bits: 100,
+        ..create_chat_message(&simulation, "test_bot1", "Cheer100 3")
+    };
+    // an ordinary drop during the splash screen is ignored
+    let sword = create_chat_message(&simulation, "test_bot2", "#sword 3");
+    receive_from_game.try_iter().for_each(drop);
+
+    assert!(simulation.create_commands(&[cheer, sword]).is_empty());
+    let chat: Vec<String> = receive_from_game.try_iter().collect();
+    assert_eq!(
+        chat,
+        vec!["@test_bot1 thanks! Your cheer drops as soon as the round is being played"]
+    );
+
+    tick_until_playing(&mut simulation);
+    let commands = simulation.create_commands(&[]);
+    simulation.tick(&InputState::default(), commands);
+    let stats = simulation.round_stats();
+    assert_eq!(stats["test_bot1"].drops.get("snake"), Some(&5));
+    assert!(!stats.contains_key("test_bot2"));
+}
+
+#[test]
+fn paid_drops_that_cant_happen_are_answered() {
+    let (mut simulation, receive_from_game) = create_simulation();
+    tick_until_playing(&mut simulation);
+    let ban = ReplayChatMessage {
+        badges: vec!["moderator".to_owned()],
+        ..create_chat_message(&simulation, "a_moderator", "!gts ban test_bot1")
+    };
+    simulation.create_commands(&[ban]);
+    let redemption = ReplayChatMessage {
+        custom_reward_id: Some("heart-reward".to_owned()),
+        ..create_chat_message(&simulation, "test_bot1", "#heart 3")
+    };
+    receive_from_game.try_iter().for_each(drop);
+
+    assert!(simulation.create_commands(&[redemption]).is_empty());
+    assert_eq!(
+        receive_from_game.try_iter().last().as_deref(),
+        Some("@test_bot1 you can't drop anything for the rest of the session, so your redemption didn't drop anything")
+    );
+
+    // outside of session mode there's no next round to hold a cheer for
+    let game = Config::default().game;
+    tick_for(&mut simulation, game.duration_to_ticks(game.game_time()));
+    assert!(simulation.running_state() == RunningState::PlayerWon);
+    let cheer = ReplayChatMessage {
+        bits: 500,
+        ..create_chat_message(&simulation, "test_bot2", "Cheer500")
+    };
+    assert!(simulation.create_commands(&[cheer]).is_empty());
+    assert_eq!(
+        receive_from_game.try_iter().last().as_deref(),
+        Some("@test_bot2 the game is over, so your cheer couldn't drop anything")
+    );
+}
+
+#[test]
+fn chatters_can_join_a_team_before_the_round_starts() {
+    let (send_to_chat, receive_from_game) = channel();
+    let mut config = Config::default();
+    config.teams.enabled = true;
+    let mut simulation = Simulation::new(
+        send_to_chat,
+        ARENA_SIZE,
+        SEED,
+        config,
+        ObjectCatalog::default(),
+    );
+    receive_from_game.try_iter().for_each(drop);
+
+    let join = create_chat_message(&simulation, "test_bot1", "!join red");
+    assert!(simulation.create_commands(&[join]).is_empty());
+    assert_eq!(
+        receive_from_game.try_iter().last().as_deref(),
+        Some("@test_bot1 joined the red team")
+    );
+
+    tick_until_playing(&mut simulation);
+    let sword = create_chat_message(&simulation, "test_bot1", "#sword 3");
+    let commands = simulation.create_commands(&[sword]);
+    assert_eq!(commands[0].chatter.team, None);
+    simulation.tick(&InputState::default(), commands);
+    assert_eq!(
+        simulation.team_scores(),
+        vec![(Team::Red, 1), (Team::Blue, 0)]
+    );
+}
@@ -0,0 +1,30 @@
+use get_the_streamer_game::team::{Team, Teams};
+
+#[test]
+fn chatters_keep_the_team_they_join() {
+    let mut teams = Teams::default();
+
+    assert_eq!(Team::parse("Blue"), Some(Team::Blue));
+    assert_eq!(Team::parse("green"), None);
+    assert_eq!(Team::parse_join("!join red please"), Some("red"));
+    assert_eq!(Team::parse_join("!join"), Some(""));
+    assert_eq!(Team::parse_join("#sword !join"), None);
+    assert_eq!(teams.join("XiTheDwarf", Team::Blue), Ok(()));
+    assert_eq!(teams.join("xithedwarf", Team::Red), Err(Team::Blue));
+    assert_eq!(teams.get("xithedwarf"), Some(Team::Blue));
+    assert_eq!(teams.get_or_assign("xithedwarf"), Team::Blue);
+}
+
+#[test]
+fn chatters_without_a_team_go_on_the_smaller_one() {
+    let mut teams = Teams::default();
+    teams.join("a", Team::Red).unwrap();
+    teams.join("b", Team::Red).unwrap();
+
+    assert_eq!(teams.get_or_assign("c"), Team::Blue);
+    assert_eq!(teams.get_or_assign("d"), Team::Blue);
+    // ties go to red
+    assert_eq!(teams.get_or_assign("e"), Team::Red);
+    assert_eq!(teams.size(Team::Red), 3);
+    assert_eq!(teams.size(Team::Blue), 2);
+}
@@ -0,0 +1,75 @@
+use get_the_streamer_game::chatter::Chatter;
+use get_the_streamer_game::object_catalog::ObjectCatalog;
+use get_the_streamer_game::vote::{Vote, VoteTally};
+
+fn chatter(name: &str) -> Chatter {
+    Chatter::new(name.to_owned(), (0, 0, 0), false)
+}
+
+fn vote(object: &str, column: Option<u8>) -> Vote {
+    Vote {
+        object: object.to_owned(),
+        column,
+    }
+}
+
+#[test]
+fn votes_are_parsed_with_an_optional_column() {
+    let catalog = ObjectCatalog::default();
+
+    assert_eq!(
+        Vote::parse("#vote sword 4", &catalog, 10),
+        Some(Ok(vote("sword", Some(4))))
+    );
+    assert_eq!(
+        Vote::parse("#vote #snake", &catalog, 10),
+        Some(Ok(vote("snake", None)))
+    );
+    assert_eq!(Vote::parse("#sword 4", &catalog, 10), None);
+    assert!(matches!(
+        Vote::parse("#vote dragon 4", &catalog, 10),
+        Some(Err(_))
+    ));
+    assert!(matches!(
+        Vote::parse("#vote sword 10", &catalog, 10),
+        Some(Err(_))
+    ));
+}
+
+#[test]
+fn the_most_votes_win_once_the_window_closes() {
+    let mut tally = VoteTally::default();
+    tally.cast(chatter("a"), vote("fire", Some(1)), 100, 600);
+    tally.cast(chatter("b"), vote("sword", Some(4)), 200, 600);
+    tally.cast(chatter("c"), vote("sword", Some(4)), 300, 600);
+    // changing a vote doesn't count twice
+    tally.cast(chatter("a"), vote("sword", Some(1)), 400, 600);
+    tally.cast(chatter("a"), vote("fire", Some(1)), 500, 600);
+
+    assert_eq!(
+        tally.object_counts(),
+        vec![("sword".to_owned(), 2), ("fire".to_owned(), 1)]
+    );
+    assert_eq!(tally.ticks_left(600), Some(100));
+    assert_eq!(tally.close(699), None);
+
+    let result = tally.close(700).unwrap();
+    assert_eq!(result.object, "sword");
+    assert_eq!(result.column, Some(4));
+    assert_eq!(result.voters, 3);
+    assert_eq!(result.leader.name, "b");
+    assert_eq!(tally.ticks_left(700), None);
+    assert_eq!(tally.close(10_000), None);
+}
+
+#[test]
+fn ties_go_to_the_first_thing_voted_for() {
+    let mut tally = VoteTally::default();
+    tally.cast(chatter("a"), vote("snake", None), 0, 10);
+    tally.cast(chatter("b"), vote("fire", Some(2)), 0, 10);
+
+    let result = tally.close(10).unwrap();
+    assert_eq!(result.object, "snake");
+    assert_eq!(result.column, Some(2));
+    assert_eq!(result.leader.name, "a");
+}